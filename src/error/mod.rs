@@ -0,0 +1,69 @@
+//!Contains the errors that simulation steps and saved states can report
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "binary")]
+use bincode;
+
+///An error from a simulation step that would have left a particle in an unusable state or was given bad input
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SimError {
+    ///The new position contained a NaN or infinite component.
+    NonFinitePosition,
+    ///The new velocity contained a NaN or infinite component.
+    NonFiniteVelocity,
+    ///An edge (i, j) refers to a particle index that is not in the system.
+    EdgeOutOfRange(usize, usize),
+    ///An edge (i, i) joins a particle to itself.
+    SelfEdge(usize),
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SimError::NonFinitePosition => write!(f, "the step produced a position that is not finite"),
+            SimError::NonFiniteVelocity => write!(f, "the step produced a velocity that is not finite"),
+            SimError::EdgeOutOfRange(i, j) => write!(f, "the edge ({}, {}) refers to a missing particle", i, j),
+            SimError::SelfEdge(i) => write!(f, "the edge ({}, {}) joins a particle to itself", i, i),
+        }
+    }
+}
+
+impl Error for SimError {}
+
+///An error from saving or loading the binary state of a ParticleSystem
+#[cfg(feature = "binary")]
+#[derive(Debug)]
+pub enum StateError {
+    ///Reading, writing, or decoding the state failed.
+    Encoding(bincode::Error),
+    ///The state was not written by save, or was saved with a different vector dimension or scalar size.
+    Header,
+}
+
+#[cfg(feature = "binary")]
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StateError::Encoding(ref e) => write!(f, "the state could not be encoded or decoded: {}", e),
+            StateError::Header => write!(f, "the state header does not match the particle type it is loaded as"),
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+impl Error for StateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            StateError::Encoding(ref e) => Some(e),
+            StateError::Header => None,
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+impl From<bincode::Error> for StateError {
+    fn from(e: bincode::Error) -> Self {
+        StateError::Encoding(e)
+    }
+}
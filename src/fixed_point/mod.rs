@@ -0,0 +1,441 @@
+//!Contains a fixed point scalar that makes simulations bit identical across machines
+
+extern crate num;
+use self::num::{Float, Num, NumCast, ToPrimitive, FromPrimitive, Zero, One};
+use self::num::traits::{ParseFloatError, FloatErrorKind};
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg};
+use std::num::FpCategory;
+use std::fmt;
+
+const FRACTION_BITS: u32 = 32;
+const ONE: i64 = 1 << FRACTION_BITS;
+
+///A signed Q32.32 fixed point number that can be used as the scalar D of every vector and particle.
+///
+///Addition, subtraction, multiplication, division, sqrt, powi, and rounding are done with integer arithmetic, so
+///a simulation that only uses those (like gravitate, hooke, and advance) produces the same bits on every machine.
+///The transcendental functions like sin, ln, and powf go through f64 and are only as reproducible as the platform.
+///
+///There is no NaN or infinity. Dividing by zero saturates to max_value or min_value, and infinity and nan return
+///max_value. Every arithmetic result that doesn't fit, including addition, subtraction, negation, and abs, is
+///saturated as well, so nothing overflows or wraps. A function that would return NaN for an input outside of its
+///domain, like acos of 2 or ln of a negative number, returns zero, the same as sqrt of a negative number.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct FixedPoint(i64);
+
+impl FixedPoint {
+    ///Create a number from its raw representation, which is the value multiplied by 2^32.
+    pub fn from_bits(bits: i64) -> Self {
+        FixedPoint(bits)
+    }
+
+    ///The raw representation, which is the value multiplied by 2^32.
+    pub fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    fn saturate(wide: i128) -> Self {
+        FixedPoint(if wide > i64::MAX as i128 {
+            i64::MAX
+        } else if wide < i64::MIN as i128 {
+            i64::MIN
+        } else {
+            wide as i64
+        })
+    }
+
+    fn via_f64<F>(self, f: F) -> Self
+        where F: FnOnce(f64) -> f64
+    {
+        FixedPoint::from_f64(f(self.to_f64().unwrap())).unwrap_or(FixedPoint::zero())
+    }
+}
+
+impl fmt::Display for FixedPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64().unwrap(), f)
+    }
+}
+
+impl Add for FixedPoint {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        FixedPoint(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for FixedPoint {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        FixedPoint(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for FixedPoint {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        FixedPoint::saturate((self.0 as i128 * rhs.0 as i128) >> FRACTION_BITS)
+    }
+}
+
+impl Div for FixedPoint {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        if rhs.0 == 0 {
+            return if self.0 > 0 {
+                FixedPoint::max_value()
+            } else if self.0 < 0 {
+                FixedPoint::min_value()
+            } else {
+                FixedPoint::zero()
+            };
+        }
+        FixedPoint::saturate(((self.0 as i128) << FRACTION_BITS) / rhs.0 as i128)
+    }
+}
+
+impl Rem for FixedPoint {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        //The remainder of a division by zero saturates the same as the division does.
+        if rhs.0 == 0 {
+            return self / rhs;
+        }
+        //The only remainder that overflows is min_value % -1, which is zero.
+        FixedPoint(self.0.checked_rem(rhs.0).unwrap_or(0))
+    }
+}
+
+impl Neg for FixedPoint {
+    type Output = Self;
+    fn neg(self) -> Self {
+        FixedPoint(self.0.saturating_neg())
+    }
+}
+
+impl Zero for FixedPoint {
+    fn zero() -> Self {
+        FixedPoint(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for FixedPoint {
+    fn one() -> Self {
+        FixedPoint(ONE)
+    }
+}
+
+impl Num for FixedPoint {
+    type FromStrRadixErr = ParseFloatError;
+    ///Parse the same strings as f64, except that NaN is an error since it has no fixed point value.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseFloatError> {
+        <f64 as Num>::from_str_radix(s, radix)
+            .and_then(|f| FixedPoint::from_f64(f).ok_or(ParseFloatError{kind: FloatErrorKind::Invalid}))
+    }
+}
+
+impl ToPrimitive for FixedPoint {
+    fn to_i64(&self) -> Option<i64> {
+        //Shifting rounds toward negative infinity, so round toward zero like a float cast does.
+        Some((self.0 + if self.0 < 0 {ONE - 1} else {0}) >> FRACTION_BITS)
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.to_i64().and_then(|i| i.to_u64())
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.0 as f64 / ONE as f64)
+    }
+}
+
+impl FromPrimitive for FixedPoint {
+    fn from_i64(n: i64) -> Option<Self> {
+        n.checked_mul(ONE).map(FixedPoint)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        n.to_i64().and_then(FixedPoint::from_i64)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        let bits = (n * ONE as f64).round();
+        if bits.is_nan() {
+            None
+        } else {
+            //Out of range values saturate, the same as a float to integer cast.
+            Some(FixedPoint(bits as i64))
+        }
+    }
+}
+
+impl NumCast for FixedPoint {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        n.to_f64().and_then(FixedPoint::from_f64)
+    }
+}
+
+impl Float for FixedPoint {
+    fn nan() -> Self {
+        FixedPoint::max_value()
+    }
+    fn infinity() -> Self {
+        FixedPoint::max_value()
+    }
+    fn neg_infinity() -> Self {
+        FixedPoint::min_value()
+    }
+    fn neg_zero() -> Self {
+        FixedPoint(0)
+    }
+    fn min_value() -> Self {
+        FixedPoint(i64::MIN)
+    }
+    fn min_positive_value() -> Self {
+        FixedPoint(1)
+    }
+    fn epsilon() -> Self {
+        FixedPoint(1)
+    }
+    fn max_value() -> Self {
+        FixedPoint(i64::MAX)
+    }
+    fn is_nan(self) -> bool {
+        false
+    }
+    fn is_infinite(self) -> bool {
+        false
+    }
+    fn is_finite(self) -> bool {
+        true
+    }
+    fn is_normal(self) -> bool {
+        self.0 != 0
+    }
+    fn classify(self) -> FpCategory {
+        if self.0 == 0 {FpCategory::Zero} else {FpCategory::Normal}
+    }
+    fn floor(self) -> Self {
+        FixedPoint(self.0 & !(ONE - 1))
+    }
+    fn ceil(self) -> Self {
+        -(-self).floor()
+    }
+    fn round(self) -> Self {
+        //Round half away from zero like f64::round.
+        let half = FixedPoint(ONE / 2);
+        if self.0 < 0 {-(-self + half).floor()} else {(self + half).floor()}
+    }
+    fn trunc(self) -> Self {
+        if self.0 < 0 {self.ceil()} else {self.floor()}
+    }
+    fn fract(self) -> Self {
+        self - self.trunc()
+    }
+    fn abs(self) -> Self {
+        FixedPoint(self.0.saturating_abs())
+    }
+    fn signum(self) -> Self {
+        FixedPoint(self.0.signum() * ONE)
+    }
+    fn is_sign_positive(self) -> bool {
+        self.0 >= 0
+    }
+    fn is_sign_negative(self) -> bool {
+        self.0 < 0
+    }
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+    fn recip(self) -> Self {
+        FixedPoint::one() / self
+    }
+    fn powi(self, n: i32) -> Self {
+        //Square and multiply so that the rounding is the same on every machine.
+        let (mut base, mut exp, mut result) = (self, n.unsigned_abs(), FixedPoint::one());
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        if n < 0 {result.recip()} else {result}
+    }
+    fn powf(self, n: Self) -> Self {
+        self.via_f64(|f| f.powf(n.to_f64().unwrap()))
+    }
+    fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return FixedPoint::zero();
+        }
+        //The square root of bits * 2^32 is the square root of the value in the same fixed point format.
+        let square = (self.0 as u128) << FRACTION_BITS;
+        let mut root = 1u128 << ((128 - square.leading_zeros()) / 2 + 1);
+        loop {
+            let next = (root + square / root) / 2;
+            if next >= root {
+                break;
+            }
+            root = next;
+        }
+        FixedPoint(root as i64)
+    }
+    fn exp(self) -> Self {
+        self.via_f64(f64::exp)
+    }
+    fn exp2(self) -> Self {
+        self.via_f64(f64::exp2)
+    }
+    fn ln(self) -> Self {
+        self.via_f64(f64::ln)
+    }
+    fn log(self, base: Self) -> Self {
+        self.via_f64(|f| f.log(base.to_f64().unwrap()))
+    }
+    fn log2(self) -> Self {
+        self.via_f64(f64::log2)
+    }
+    fn log10(self) -> Self {
+        self.via_f64(f64::log10)
+    }
+    fn max(self, other: Self) -> Self {
+        if self >= other {self} else {other}
+    }
+    fn min(self, other: Self) -> Self {
+        if self <= other {self} else {other}
+    }
+    fn abs_sub(self, other: Self) -> Self {
+        if self > other {self - other} else {FixedPoint::zero()}
+    }
+    fn cbrt(self) -> Self {
+        self.via_f64(f64::cbrt)
+    }
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+    fn sin(self) -> Self {
+        self.via_f64(f64::sin)
+    }
+    fn cos(self) -> Self {
+        self.via_f64(f64::cos)
+    }
+    fn tan(self) -> Self {
+        self.via_f64(f64::tan)
+    }
+    fn asin(self) -> Self {
+        self.via_f64(f64::asin)
+    }
+    fn acos(self) -> Self {
+        self.via_f64(f64::acos)
+    }
+    fn atan(self) -> Self {
+        self.via_f64(f64::atan)
+    }
+    fn atan2(self, other: Self) -> Self {
+        self.via_f64(|f| f.atan2(other.to_f64().unwrap()))
+    }
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+    fn exp_m1(self) -> Self {
+        self.via_f64(f64::exp_m1)
+    }
+    fn ln_1p(self) -> Self {
+        self.via_f64(f64::ln_1p)
+    }
+    fn sinh(self) -> Self {
+        self.via_f64(f64::sinh)
+    }
+    fn cosh(self) -> Self {
+        self.via_f64(f64::cosh)
+    }
+    fn tanh(self) -> Self {
+        self.via_f64(f64::tanh)
+    }
+    fn asinh(self) -> Self {
+        self.via_f64(f64::asinh)
+    }
+    fn acosh(self) -> Self {
+        self.via_f64(f64::acosh)
+    }
+    fn atanh(self) -> Self {
+        self.via_f64(f64::atanh)
+    }
+    fn integer_decode(self) -> (u64, i16, i8) {
+        (self.0.unsigned_abs(), -(FRACTION_BITS as i16), if self.0 < 0 {-1} else {1})
+    }
+}
+
+#[test]
+fn arithmetic_test() {
+    let f = |x: f64| FixedPoint::from_f64(x).unwrap();
+    assert_eq!(f(1.5) + f(2.25), f(3.75));
+    assert_eq!(f(1.5) * f(-2.0), f(-3.0));
+    assert_eq!(f(3.0) / f(4.0), f(0.75));
+    assert_eq!(f(2.25).sqrt(), f(1.5));
+    assert_eq!(f(1.5).powi(3), f(3.375));
+    assert_eq!(f(0.5).powi(-2), f(4.0));
+    assert_eq!((f(-2.5).floor(), f(-2.5).ceil(), f(-2.5).round(), f(-2.5).trunc()), (f(-3.0), f(-2.0), f(-3.0), f(-2.0)));
+    assert_eq!(f(1.0) / f(0.0), FixedPoint::max_value());
+    assert_eq!(f(-7.75).to_i64(), Some(-7));
+    assert!((f(2.0).sqrt().to_f64().unwrap() - 2.0f64.sqrt()).abs() < 1e-9);
+    assert_eq!(format!("{}", f(-0.25)), "-0.25");
+}
+
+#[test]
+fn saturating_test() {
+    let (max, min, one) = (FixedPoint::max_value(), FixedPoint::min_value(), FixedPoint::one());
+    assert_eq!(max + one, max);
+    assert_eq!(min - one, min);
+    assert_eq!(one / FixedPoint::zero() + one, max);
+    assert_eq!(-FixedPoint::neg_infinity(), max);
+    assert_eq!(min.abs(), max);
+    assert_eq!(one % FixedPoint::zero(), max);
+    assert_eq!(-one % FixedPoint::zero(), min);
+    assert_eq!(min % FixedPoint::from_bits(-1), FixedPoint::zero());
+    let f = |x: f64| FixedPoint::from_f64(x).unwrap();
+    assert_eq!(f(7.5) % f(2.0), f(1.5));
+}
+
+#[test]
+fn out_of_domain_test() {
+    let f = |x: f64| FixedPoint::from_f64(x).unwrap();
+    assert_eq!(f(2.0).acos(), FixedPoint::zero());
+    assert_eq!(f(-2.0).asin(), FixedPoint::zero());
+    assert_eq!(f(-1.0).ln(), FixedPoint::zero());
+    assert_eq!(f(-4.0).sqrt(), FixedPoint::zero());
+    assert_eq!(f(-8.0).powf(f(0.5)), FixedPoint::zero());
+    //Results that are infinite in f64 saturate instead.
+    assert_eq!(FixedPoint::zero().ln(), FixedPoint::min_value());
+    assert!(FixedPoint::from_str_radix("NaN", 10).is_err());
+    assert!(FixedPoint::from_str_radix("x", 10).is_err());
+    assert_eq!(FixedPoint::from_str_radix("inf", 10).ok(), Some(FixedPoint::max_value()));
+    assert_eq!(FixedPoint::from_str_radix("-2.5", 10).ok(), Some(f(-2.5)));
+}
+
+#[test]
+fn deterministic_test() {
+    use super::vector::*;
+    use super::particle::*;
+    type P = BasicParticle<Cartesian2<FixedPoint>, FixedPoint>;
+    let f = |x: f64| FixedPoint::from_f64(x).unwrap();
+    let mut a = P::new(f(1.0), Cartesian2::new(f(-0.5), f(0.0)), Cartesian2::new(f(0.0), f(-0.6)), f(1.0));
+    let mut b = P::new(f(1.0), Cartesian2::new(f(0.5), f(0.0)), Cartesian2::new(f(0.0), f(0.6)), f(1.0));
+    for _ in 0..2000 {
+        gravitate(&a, &b, f(1.0));
+        a.advance(f(0.001));
+        b.advance(f(0.001));
+    }
+    let bits: Vec<_> = [a.position.x, a.position.y, a.velocity.x, a.velocity.y, b.position.x, b.position.y].iter()
+        .map(|c| c.to_bits()).collect();
+    //Every machine must produce exactly these bits, so any change to the integer arithmetic is caught here.
+    assert_eq!(bits, vec![108269303, 1512913831, -3567543529, 1261936340, -108273316, -1512917838]);
+    //The pair is bound, so it must still be close together after orbiting.
+    let separation = FixedPoint::from_bits(bits[4] - bits[0]).to_f64().unwrap();
+    assert!(separation.abs() < 2.0);
+}
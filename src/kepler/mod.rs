@@ -0,0 +1,191 @@
+//!Contains the analytic solution of the gravitational two body problem, which integrators can be validated against
+
+extern crate num;
+use self::num::{Float, FromPrimitive};
+use super::vector::{Vector, CrossVector, Cartesian2, Cartesian3};
+
+///Solve Kepler's equation M = E - e sin(E) for the eccentric anomaly E with Newton's method.
+fn eccentric_anomaly<D>(mean_anomaly: D, e: D) -> D
+    where D: Float
+{
+    //Starting at pi for large eccentricities keeps the iteration from overshooting near periapsis.
+    let mut anomaly = if e > num::cast(0.8).unwrap() {
+        num::cast::<_, D>(::std::f64::consts::PI).unwrap() * mean_anomaly.signum()
+    } else {
+        mean_anomaly
+    };
+    for _ in 0..64 {
+        let step = (anomaly - e * anomaly.sin() - mean_anomaly) / (D::one() - e * anomaly.cos());
+        anomaly = anomaly - step;
+        if step.abs() <= D::epsilon() * (D::one() + anomaly.abs()) {
+            break;
+        }
+    }
+    anomaly
+}
+
+///Find the eccentric anomaly at time t, with the mean anomaly wrapped to (-pi, pi] so Newton's method converges.
+fn anomaly_at<D>(mu: D, a: D, e: D, t: D) -> D
+    where D: Float
+{
+    let tau = num::cast::<_, D>(2.0 * ::std::f64::consts::PI).unwrap();
+    let mean = (mu / (a * a * a)).sqrt() * t;
+    eccentric_anomaly(mean - (mean / tau).round() * tau, e)
+}
+
+///Compute the position of a test mass on an elliptic Kepler orbit at time t.
+///
+///The attracting body sits at the origin with the gravitational parameter mu, which is the gravitational constant
+///times its quanta, so the test mass accelerates by mu / r^2 towards it. The orbit has the semi-major axis a and the
+///eccentricity e, which must be in [0, 1). The test mass is at periapsis on the positive x axis at t = 0 and orbits
+///counterclockwise, returning to the start every period of 2 pi sqrt(a^3 / mu).
+pub fn two_body_position<D>(mu: D, a: D, e: D, t: D) -> Cartesian2<D>
+    where D: Float
+{
+    let anomaly = anomaly_at(mu, a, e, t);
+    Cartesian2{
+        x: a * (anomaly.cos() - e),
+        y: a * (D::one() - e * e).sqrt() * anomaly.sin(),
+    }
+}
+
+///Compute the velocity of the test mass at time t on the same orbit as two_body_position.
+pub fn two_body_velocity<D>(mu: D, a: D, e: D, t: D) -> Cartesian2<D>
+    where D: Float
+{
+    let anomaly = anomaly_at(mu, a, e, t);
+    let speed = (mu * a).sqrt() / (a * (D::one() - e * anomaly.cos()));
+    Cartesian2{
+        x: -speed * anomaly.sin(),
+        y: speed * (D::one() - e * e).sqrt() * anomaly.cos(),
+    }
+}
+
+///The classical elements of a Kepler orbit, with every angle in radians
+#[derive(Copy, Clone, Debug)]
+pub struct OrbitalElements<D> {
+    ///Half of the longest diameter of the orbit, which is negative for a hyperbolic orbit.
+    pub semi_major_axis: D,
+    pub eccentricity: D,
+    ///The tilt of the orbit from the xy plane in [0, pi], which is over pi / 2 for a retrograde orbit.
+    pub inclination: D,
+    ///The angle from the x axis to where the orbit crosses the xy plane going up in z.
+    pub longitude_of_ascending_node: D,
+    ///The angle from the ascending node to periapsis along the orbit.
+    pub argument_of_periapsis: D,
+    ///The angle from periapsis to the position along the orbit.
+    pub true_anomaly: D,
+}
+
+//Wrap an angle from atan2 into [0, 2 pi).
+fn positive_angle<D>(angle: D) -> D
+    where D: Float
+{
+    if angle < D::zero() {
+        angle + num::cast(2.0 * ::std::f64::consts::PI).unwrap()
+    } else {
+        angle
+    }
+}
+
+///Compute the orbital elements of a body from its position and velocity relative to a central mass with the
+///gravitational parameter mu, which is the inverse of two_body_position and two_body_velocity.
+///
+///Angles that are undefined are measured from a fallback instead. An orbit in the xy plane has its ascending node
+///on the x axis, and a circular orbit has its periapsis at the ascending node.
+///
+///Returns None for a radial trajectory, where the body moves straight towards or away from the central mass. Its
+///angular momentum is zero, so there is no plane of the orbit to measure the angles in.
+pub fn orbital_elements<D>(pos: &Cartesian3<D>, vel: &Cartesian3<D>, mu: D) -> Option<OrbitalElements<D>>
+    where D: Float + FromPrimitive
+{
+    let tolerance = D::epsilon().sqrt();
+    let momentum = Cartesian3::cross(pos, vel);
+    let radius = pos.displacement();
+    if momentum.displacement() <= tolerance * radius * vel.displacement() {
+        return None;
+    }
+    let normal = momentum.normalized();
+    let energy = vel.displacement_squared() / (D::one() + D::one()) - mu / radius;
+    let eccentricity = Cartesian3::cross(vel, &momentum) / mu - *pos / radius;
+    let e = eccentricity.displacement();
+
+    //The node points to where the orbit rises through the xy plane.
+    let node = Cartesian3::new(-momentum.y, momentum.x, D::zero());
+    let node = if node.displacement() > tolerance * momentum.displacement() {
+        node.normalized()
+    } else {
+        Cartesian3::new(D::one(), D::zero(), D::zero())
+    };
+    let periapsis = if e > tolerance {eccentricity / e} else {node};
+    //The signed angle from one direction to another in the plane of the orbit.
+    let angle = |from: &Cartesian3<D>, to: &Cartesian3<D>| positive_angle(
+        Cartesian3::dot(&normal, &Cartesian3::cross(from, to)).atan2(Cartesian3::dot(from, to)));
+
+    Some(OrbitalElements{
+        semi_major_axis: -mu / (energy + energy),
+        eccentricity: e,
+        inclination: normal.z.max(-D::one()).min(D::one()).acos(),
+        longitude_of_ascending_node: positive_angle(node.y.atan2(node.x)),
+        argument_of_periapsis: angle(&node, &periapsis),
+        true_anomaly: angle(&periapsis, pos),
+    })
+}
+
+#[test]
+fn orbital_elements_test() {
+    let mu = 2.5;
+    let x = Cartesian3::new(1.0, 0.0, 0.0);
+    let z = Cartesian3::new(0.0, 0.0, 1.0);
+    for &(a, e, inclination, node, periapsis, t) in &[
+        (1.5, 0.3, 0.4, 1.0, 2.0, 0.7),
+        (0.8, 0.9, 2.5, 4.0, 0.5, 3.1),
+        (2.0, 0.05, 1.2, 5.5, 3.5, -1.0),
+    ] {
+        //Rotate the orbit from the xy plane with periapsis on the x axis into place.
+        let place = |v: Cartesian2<f64>| Cartesian3::from(v).rotate_around(&z, periapsis).rotate_around(&x, inclination)
+            .rotate_around(&z, node);
+        let (flat, flat_velocity) = (two_body_position(mu, a, e, t), two_body_velocity(mu, a, e, t));
+        let elements = orbital_elements(&place(flat), &place(flat_velocity), mu).unwrap();
+        assert!((elements.semi_major_axis - a).abs() < 1e-12);
+        assert!((elements.eccentricity - e).abs() < 1e-12);
+        assert!((elements.inclination - inclination).abs() < 1e-12);
+        assert!((elements.longitude_of_ascending_node - node).abs() < 1e-12);
+        assert!((elements.argument_of_periapsis - periapsis).abs() < 1e-12);
+        assert!((elements.true_anomaly - positive_angle(flat.y.atan2(flat.x))).abs() < 1e-12);
+    }
+    //A circular orbit in the xy plane measures every angle from the x axis.
+    let elements = orbital_elements(&Cartesian3::new(0.0, 2.0, 0.0), &Cartesian3::new(-1.0, 0.0, 0.0), 2.0).unwrap();
+    assert!((elements.semi_major_axis - 2.0).abs() < 1e-12 && elements.eccentricity < 1e-12);
+    assert_eq!((elements.inclination, elements.longitude_of_ascending_node), (0.0, 0.0));
+    assert!(elements.argument_of_periapsis.abs() < 1e-12);
+    assert!((elements.true_anomaly - ::std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+    //A radial trajectory has no plane of the orbit.
+    assert!(orbital_elements(&Cartesian3::new(1.0, 2.0, 0.0), &Cartesian3::new(-0.5, -1.0, 0.0), 2.0).is_none());
+    assert!(orbital_elements(&Cartesian3::new(1.0, 2.0, 0.0), &Cartesian3::new(0.0, 0.0, 0.0), 2.0).is_none());
+}
+
+#[test]
+fn leapfrog_orbit_test() {
+    use std::f64::consts::PI;
+    use super::vector::Vector;
+    use super::particle::*;
+    let (mu, a, e): (f64, f64, f64) = (1.0, 1.0, 0.5);
+    let period = 2.0 * PI * (a * a * a / mu).sqrt();
+    let mut p = BasicParticle::new(1.0, two_body_position(mu, a, e, 0.0), two_body_velocity(mu, a, e, 0.0), 1.0);
+    assert!((p.position - Cartesian2::new(0.5, 0.0)).displacement() < 1e-15);
+    let accel = |position: Cartesian2<f64>| -position * mu / position.displacement().powi(3);
+    let steps = 20000;
+    let dt = period / steps as f64;
+    let mut before = accel(p.position);
+    for i in 1..steps + 1 {
+        let after = accel(p.leapfrog_position(&before, dt));
+        p.advance_leapfrog(&before, &after, dt);
+        before = after;
+        if i % 1000 == 0 {
+            let exact = two_body_position(mu, a, e, dt * i as f64);
+            assert!((p.position - exact).displacement() < 1e-4);
+        }
+    }
+    assert!((p.position - Cartesian2::new(0.5, 0.0)).displacement() < 1e-4);
+}
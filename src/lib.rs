@@ -4,7 +4,30 @@
 //! vector geometry. It also contains various particle interactions in the particle module, which
 //! is publically used in zoom.
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "binary")]
+extern crate bincode;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 pub mod vector;
 pub mod particle;
+pub mod system;
+pub mod matrix;
+pub mod fixed_point;
+pub mod error;
+pub mod kepler;
+pub mod scenarios;
 pub use vector::*;
 pub use particle::*;
+pub use system::*;
+pub use matrix::*;
+pub use fixed_point::*;
+pub use error::*;
+pub use kepler::*;
+pub use scenarios::*;
@@ -0,0 +1,156 @@
+extern crate num;
+use self::num::Float;
+use super::vector::{Vector, Cartesian2, Cartesian3};
+use std::ops::Mul;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///A 2x2 matrix stored as rows, which transforms Cartesian2 vectors
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matrix2<D>(pub [[D; 2]; 2]);
+
+///A 3x3 matrix stored as rows, which transforms Cartesian3 vectors
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matrix3<D>(pub [[D; 3]; 3]);
+
+impl<D> Matrix2<D>
+    where D: Float
+{
+    pub fn new(rows: [[D; 2]; 2]) -> Self {
+        Matrix2(rows)
+    }
+
+    pub fn identity() -> Self {
+        let (o, l) = (D::zero(), D::one());
+        Matrix2([[l, o], [o, l]])
+    }
+
+    ///A counterclockwise rotation by angle in radians
+    pub fn from_rotation(angle: D) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Matrix2([[cos, -sin], [sin, cos]])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = &self.0;
+        Matrix2([[m[0][0], m[1][0]], [m[0][1], m[1][1]]])
+    }
+}
+
+impl<D> Matrix3<D>
+    where D: Float
+{
+    pub fn new(rows: [[D; 3]; 3]) -> Self {
+        Matrix3(rows)
+    }
+
+    pub fn identity() -> Self {
+        let (o, l) = (D::zero(), D::one());
+        Matrix3([[l, o, o], [o, l, o], [o, o, l]])
+    }
+
+    ///A counterclockwise rotation by angle in radians around axis, following the right hand rule
+    ///
+    ///The axis does not need to be normalized, but it must not be zero.
+    pub fn from_axis_angle(axis: Cartesian3<D>, angle: D) -> Self
+        where Cartesian3<D>: Vector<D>
+    {
+        let Cartesian3{x, y, z} = axis.normalized();
+        let (sin, cos) = angle.sin_cos();
+        let t = D::one() - cos;
+        Matrix3([
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos],
+        ])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = &self.0;
+        Matrix3([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+}
+
+impl<D> Mul for Matrix2<D>
+    where D: Float
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let cell = |i: usize, j: usize| self.0[i][0] * rhs.0[0][j] + self.0[i][1] * rhs.0[1][j];
+        Matrix2([[cell(0, 0), cell(0, 1)], [cell(1, 0), cell(1, 1)]])
+    }
+}
+
+impl<D> Mul for Matrix3<D>
+    where D: Float
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let cell = |i: usize, j: usize| {
+            self.0[i][0] * rhs.0[0][j] + self.0[i][1] * rhs.0[1][j] + self.0[i][2] * rhs.0[2][j]
+        };
+        let row = |i: usize| [cell(i, 0), cell(i, 1), cell(i, 2)];
+        Matrix3([row(0), row(1), row(2)])
+    }
+}
+
+impl<D> Mul<Cartesian2<D>> for Matrix2<D>
+    where D: Float
+{
+    type Output = Cartesian2<D>;
+    fn mul(self, rhs: Cartesian2<D>) -> Cartesian2<D> {
+        let m = &self.0;
+        Cartesian2{
+            x: m[0][0] * rhs.x + m[0][1] * rhs.y,
+            y: m[1][0] * rhs.x + m[1][1] * rhs.y,
+        }
+    }
+}
+
+impl<D> Mul<Cartesian3<D>> for Matrix3<D>
+    where D: Float
+{
+    type Output = Cartesian3<D>;
+    fn mul(self, rhs: Cartesian3<D>) -> Cartesian3<D> {
+        let m = &self.0;
+        Cartesian3{
+            x: m[0][0] * rhs.x + m[0][1] * rhs.y + m[0][2] * rhs.z,
+            y: m[1][0] * rhs.x + m[1][1] * rhs.y + m[1][2] * rhs.z,
+            z: m[2][0] * rhs.x + m[2][1] * rhs.y + m[2][2] * rhs.z,
+        }
+    }
+}
+
+#[test]
+fn rotate_velocity_2d() {
+    use std::f64::consts::FRAC_PI_2;
+    use super::particle::*;
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(2.0, 1.0), 1.0);
+    p.velocity = Matrix2::from_rotation(FRAC_PI_2) * p.velocity;
+    assert!((p.velocity - Cartesian2::new(-1.0, 2.0)).displacement() < 1e-12);
+    //A quarter turn four times is the identity.
+    let quarter = Matrix2::from_rotation(FRAC_PI_2);
+    let full = quarter * quarter * quarter * quarter;
+    assert!((full * p.velocity - Matrix2::identity() * p.velocity).displacement() < 1e-12);
+}
+
+#[test]
+fn rotate_velocity_3d() {
+    use std::f64::consts::FRAC_PI_2;
+    use super::particle::*;
+    let mut p = BasicParticle::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(2.0, 1.0, 3.0), 1.0);
+    let r = Matrix3::from_axis_angle(Cartesian3::new(0.0, 0.0, 5.0), FRAC_PI_2);
+    p.velocity = r * p.velocity;
+    assert!((p.velocity - Cartesian3::new(-1.0, 2.0, 3.0)).displacement() < 1e-12);
+    //Rotations are orthogonal, so the transpose undoes them.
+    p.velocity = r.transpose() * p.velocity;
+    assert!((p.velocity - Cartesian3::new(2.0, 1.0, 3.0)).displacement() < 1e-12);
+    let x = Matrix3::from_axis_angle(Cartesian3::new(1.0, 0.0, 0.0), FRAC_PI_2) * Cartesian3::new(0.0, 1.0, 0.0);
+    assert!((x - Cartesian3::new(0.0, 0.0, 1.0)).displacement() < 1e-12);
+}
@@ -0,0 +1,54 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::{Vector, Ball};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///BallParticle is a BasicParticle with a radius so that it can take part in interactions that need a Ball.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "V: Deserialize<'de> + num::Zero, D: Deserialize<'de>")))]
+pub struct BallParticle<V, D> {
+    pub particle: BasicParticle<V, D>,
+    pub radius: D,
+}
+
+impl<V, D> BallParticle<V, D> {
+    pub fn new(quanta: D, position: V, velocity: V, inertia: D, radius: D) -> Self
+        where V: num::Zero
+    {
+        BallParticle{
+            particle: BasicParticle::new(quanta, position, velocity, inertia),
+            radius: radius,
+        }
+    }
+}
+
+impl<V, D> BallParticle<V, D>
+    where V: Vector<D>, D: Float
+{
+    ///Merge two balls into one that conserves their quanta, inertia, and momentum, placed at their center of mass.
+    ///
+    ///The radius grows so that the volume of the merged ball is the sum of both volumes. Forces that were applied
+    ///to either ball but not yet advanced are dropped.
+    pub fn merge(&self, other: &Self) -> Self {
+        let inertia = self.inertia() + other.inertia();
+        let position = (self.position() * self.inertia() + other.position() * other.inertia()) / inertia;
+        let velocity = (self.momentum() + other.momentum()) / inertia;
+        let volume = V::space_ball(self.radius) + V::space_ball(other.radius);
+        let dimensions: D = num::cast(V::dimensions()).unwrap();
+        let radius = (volume / V::space_ball(D::one())).powf(dimensions.recip());
+        BallParticle::new(self.quanta() + other.quanta(), position, velocity, inertia, radius)
+    }
+}
+
+impl<V, D> Ball<D> for BallParticle<V, D>
+    where D: Float
+{
+    fn radius(&self) -> D {
+        self.radius
+    }
+}
+
+delegate_basic_particle!(BallParticle where V: Vector<D>, D: Float);
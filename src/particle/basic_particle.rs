@@ -2,16 +2,34 @@ extern crate num;
 use self::num::Float;
 use super::*;
 use super::super::Vector;
+use super::super::error::SimError;
 
 use std::cell::UnsafeCell;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 
 ///BasicParticle is the simplest object that implements PhysicsParticle without using constant values.
+///
+///When serialized, forces that have been applied but not yet advanced are left out.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "V: Deserialize<'de> + num::Zero, D: Deserialize<'de>")))]
 pub struct BasicParticle<V, D> {
     pub quanta: D,
     pub inertia: D,
     pub position: V,
     pub velocity: V,
+    #[cfg_attr(feature = "serde", serde(skip, default = "zero_force"))]
     force: UnsafeCell<V>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_accel: Option<V>,
+}
+
+#[cfg(feature = "serde")]
+fn zero_force<V>() -> UnsafeCell<V>
+    where V: num::Zero
+{
+    UnsafeCell::new(V::zero())
 }
 
 impl<V, D> BasicParticle<V, D> {
@@ -24,10 +42,91 @@ impl<V, D> BasicParticle<V, D> {
             position: position,
             velocity: velocity,
             force: UnsafeCell::new(V::zero()),
+            last_accel: None,
+        }
+    }
+
+    ///Create a particle at position with zero velocity.
+    ///
+    ///```
+    ///use zoom::{BasicParticle, Cartesian2, Velocity};
+    ///let p = BasicParticle::stationary_at(2.0, Cartesian2::new(1.0, -1.0), 3.0);
+    ///assert_eq!((p.position.x, p.position.y), (1.0, -1.0));
+    ///assert_eq!((p.velocity().x, p.velocity().y), (0.0, 0.0));
+    ///```
+    pub fn stationary_at(quanta: D, position: V, inertia: D) -> Self
+        where V: num::Zero
+    {
+        BasicParticle::new(quanta, position, V::zero(), inertia)
+    }
+
+    ///Create a particle at the origin with zero velocity.
+    ///
+    ///```
+    ///use zoom::{BasicParticle, Cartesian3, Position};
+    ///let p: BasicParticle<Cartesian3<f64>, f64> = BasicParticle::at_rest(2.0, 3.0);
+    ///assert_eq!((p.position().x, p.position().y, p.position().z), (0.0, 0.0, 0.0));
+    ///assert_eq!((p.quanta, p.inertia), (2.0, 3.0));
+    ///```
+    pub fn at_rest(quanta: D, inertia: D) -> Self
+        where V: num::Zero
+    {
+        BasicParticle::new(quanta, V::zero(), V::zero(), inertia)
+    }
+}
+
+impl<V, D> BasicParticle<V, D>
+    where V: Vector<D>, D: Float
+{
+    ///Start building a particle, where every field that is not set keeps the value from BasicParticle::default().
+    ///
+    ///```
+    ///use zoom::{BasicParticle, Cartesian2, Position, Velocity};
+    ///let p: BasicParticle<Cartesian2<f64>, f64> = BasicParticle::builder()
+    ///    .quanta(1.0)
+    ///    .inertia(1.0)
+    ///    .build();
+    ///assert_eq!((p.position().x, p.position().y), (0.0, 0.0));
+    ///assert_eq!((p.velocity().x, p.velocity().y), (0.0, 0.0));
+    ///```
+    pub fn builder() -> BasicParticleBuilder<V, D> {
+        BasicParticleBuilder{
+            particle: BasicParticle::default(),
         }
     }
 }
 
+///Builds a BasicParticle one field at a time so that its scalar fields cannot be mixed up.
+pub struct BasicParticleBuilder<V, D> {
+    particle: BasicParticle<V, D>,
+}
+
+impl<V, D> BasicParticleBuilder<V, D> {
+    pub fn quanta(mut self, quanta: D) -> Self {
+        self.particle.quanta = quanta;
+        self
+    }
+
+    pub fn inertia(mut self, inertia: D) -> Self {
+        self.particle.inertia = inertia;
+        self
+    }
+
+    pub fn position(mut self, position: V) -> Self {
+        self.particle.position = position;
+        self
+    }
+
+    pub fn velocity(mut self, velocity: V) -> Self {
+        self.particle.velocity = velocity;
+        self
+    }
+
+    pub fn build(self) -> BasicParticle<V, D> {
+        self.particle
+    }
+}
+
 impl<V, D> Clone for BasicParticle<V, D>
     where V: Clone, D: Clone
 {
@@ -38,10 +137,13 @@ impl<V, D> Clone for BasicParticle<V, D>
             position: self.position.clone(),
             velocity: self.velocity.clone(),
             force: UnsafeCell::new(unsafe{(*self.force.get()).clone()}),
+            last_accel: self.last_accel.clone(),
         }
     }
 }
 
+///The default particle is at rest at the origin with a quanta and inertia of 1, so that it takes part in every
+///interaction and can be advanced without dividing by zero.
 impl<V, D> Default for BasicParticle<V, D>
     where V: Vector<D>, D: Float
 {
@@ -52,10 +154,28 @@ impl<V, D> Default for BasicParticle<V, D>
             position: V::zero(),
             velocity: V::zero(),
             force: UnsafeCell::new(V::zero()),
+            last_accel: None,
         }
     }
 }
 
+///Formats as position: (x, y), velocity: (x, y), quanta: q, inertia: m, where the precision and other flags apply
+///to every number
+impl<V, D> fmt::Display for BasicParticle<V, D>
+    where V: fmt::Display, D: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "position: ")?;
+        fmt::Display::fmt(&self.position, f)?;
+        write!(f, ", velocity: ")?;
+        fmt::Display::fmt(&self.velocity, f)?;
+        write!(f, ", quanta: ")?;
+        fmt::Display::fmt(&self.quanta, f)?;
+        write!(f, ", inertia: ")?;
+        fmt::Display::fmt(&self.inertia, f)
+    }
+}
+
 impl<V, D> Quanta<D> for BasicParticle<V, D>
     where V: Vector<D>, D: Float
 {
@@ -88,6 +208,22 @@ impl <V, D> Velocity<V> for BasicParticle<V, D>
     }
 }
 
+impl<V, D> PositionMut<V> for BasicParticle<V, D>
+    where V: Vector<D>, D: Float
+{
+    fn set_position(&mut self, position: V) {
+        self.position = position;
+    }
+}
+
+impl<V, D> VelocityMut<V> for BasicParticle<V, D>
+    where V: Vector<D>, D: Float
+{
+    fn set_velocity(&mut self, velocity: V) {
+        self.velocity = velocity;
+    }
+}
+
 impl<V, D> Particle<V, D> for BasicParticle<V, D>
     where V: Vector<D>, D: Float
 {
@@ -102,6 +238,7 @@ impl<V, D> Particle<V, D> for BasicParticle<V, D>
         self.velocity = self.velocity + *force / self.inertia() * time;
         self.position = self.position + self.velocity * time;
         *force = V::zero();
+        self.last_accel = None;
     }
 }
 
@@ -109,3 +246,437 @@ impl<V, D> PhysicsParticle<V, D> for BasicParticle<V, D>
     where V: Vector<D>, D: Float
 {
 }
+
+impl<V, D> BasicParticle<V, D>
+    where V: Vector<D>, D: Float
+{
+    ///The force accumulated since the last step, which impulse and apply_force both add to.
+    pub fn net_force(&self) -> V {
+        unsafe {*self.force.get()}
+    }
+
+    ///Check if the quanta, inertia, position, and velocity are all within epsilon of other, ignoring forces that
+    ///have not been advanced.
+    pub fn approx_eq(&self, other: &Self, epsilon: D) -> bool {
+        (self.quanta - other.quanta).abs() <= epsilon && (self.inertia - other.inertia).abs() <= epsilon &&
+            self.position.approx_eq(&other.position, epsilon) && self.velocity.approx_eq(&other.velocity, epsilon)
+    }
+
+    ///Add a raw force to the accumulator without stepping, so forces from several sources can be summed first.
+    ///
+    ///This is the same accumulator impulse adds to, but it takes the particle mutably rather than going through the
+    ///interior cell.
+    pub fn apply_force(&mut self, f: &V) {
+        let force = self.force.get_mut();
+        *force = *force + *f;
+    }
+
+    ///Turn the accumulated force into an acceleration through the inertia, step velocity and position, and zero the
+    ///accumulator. This is the same step as advance.
+    pub fn integrate(&mut self, dt: D) {
+        self.advance(dt);
+    }
+
+    ///Same as advance, but fails without changing the particle if the new velocity or position is NaN or infinite.
+    ///
+    ///The accumulated force is kept on failure so that it can be inspected with net_force.
+    pub fn checked_advance(&mut self, time: D) -> Result<(), SimError> {
+        let velocity = self.velocity + self.net_force() / self.inertia * time;
+        if !velocity.is_finite() {
+            return Err(SimError::NonFiniteVelocity);
+        }
+        let position = self.position + velocity * time;
+        if !position.is_finite() {
+            return Err(SimError::NonFinitePosition);
+        }
+        self.advance(time);
+        Ok(())
+    }
+
+    ///Advance with semi-implicit (Euler-Cromer) integration, which updates the velocity and then moves with it.
+    ///
+    ///This is what advance already does; it exists so that code can name the method it depends on.
+    pub fn advance_semi_implicit(&mut self, dt: D) {
+        self.advance(dt);
+    }
+
+    ///Advance with explicit Euler integration, which moves with the old velocity before updating it.
+    ///
+    ///Explicit Euler gains energy in every oscillating system, so this is mainly useful as a baseline to compare
+    ///the other integrators against.
+    pub fn advance_explicit(&mut self, dt: D) {
+        let force = self.force.get_mut();
+        self.position = self.position + self.velocity * dt;
+        self.velocity = self.velocity + *force / self.inertia * dt;
+        *force = V::zero();
+        self.last_accel = None;
+    }
+
+    ///Scale the velocity down to max_speed if the particle is faster than that, keeping its direction.
+    pub fn clamp_speed(&mut self, max_speed: D) {
+        let speed = self.velocity.displacement();
+        if speed > max_speed {
+            self.velocity = self.velocity * (max_speed / speed);
+        }
+    }
+
+    ///Advance particle forward in time with the classic fourth order Runge-Kutta method instead of an Euler step.
+    ///
+    ///The accel closure computes the acceleration of the particle given a position and velocity, which lets the
+    ///net force field be sampled at each of the four stages. Any force applied through impulse is treated as
+    ///constant over the step and is consumed the same way advance consumes it.
+    pub fn advance_rk4<F>(&mut self, time: D, accel: F)
+        where F: Fn(&V, &V) -> V
+    {
+        let force = unsafe {&mut *self.force.get()};
+        let constant = *force / self.inertia;
+        let accel = |p: V, v: V| accel(&p, &v) + constant;
+        let two = D::one() + D::one();
+        let half = time / two;
+
+        let (p, v) = (self.position, self.velocity);
+        let (k1p, k1v) = (v, accel(p, v));
+        let (k2p, k2v) = (v + k1v * half, accel(p + k1p * half, v + k1v * half));
+        let (k3p, k3v) = (v + k2v * half, accel(p + k2p * half, v + k2v * half));
+        let (k4p, k4v) = (v + k3v * time, accel(p + k3p * time, v + k3v * time));
+
+        let sixth = time / (two + two + two);
+        self.position = p + (k1p + k2p * two + k3p * two + k4p) * sixth;
+        self.velocity = v + (k1v + k2v * two + k3v * two + k4v) * sixth;
+        *force = V::zero();
+        self.last_accel = None;
+    }
+
+    ///Advance particle forward in time with velocity Verlet integration, which keeps energy bounded.
+    ///
+    ///All forces must be applied at the current position before each call, since the drift of this step and the
+    ///second half-kick of the previous step both use them. The second half-kick can only happen once those forces
+    ///are known, so until the next call the velocity holds the full-kick estimate and the acceleration is retained
+    ///to correct it, which assumes the time step does not change between calls. Calling any of the other advance
+    ///methods in between discards the retained acceleration.
+    pub fn advance_verlet(&mut self, time: D) {
+        let force = unsafe {&mut *self.force.get()};
+        let accel = *force / self.inertia;
+        let half = time / (D::one() + D::one());
+        //Replace the previous half-kick estimate with the acceleration at this position.
+        if let Some(last) = self.last_accel {
+            self.velocity = self.velocity + (accel - last) * half;
+        }
+        self.position = self.position + (self.velocity + accel * half) * time;
+        self.velocity = self.velocity + accel * time;
+        self.last_accel = Some(accel);
+        *force = V::zero();
+    }
+
+    ///Same as advance, but with an acceleration computed by the caller instead of the accumulated forces.
+    ///
+    ///The velocity is kicked by accel first and then the position drifts with the new velocity. Any force applied
+    ///through impulse is added to accel and consumed the same way advance consumes it.
+    pub fn advance_with_accel(&mut self, accel: &V, dt: D) {
+        let force = self.force.get_mut();
+        self.velocity = self.velocity + (*accel + *force / self.inertia) * dt;
+        self.position = self.position + self.velocity * dt;
+        *force = V::zero();
+        self.last_accel = None;
+    }
+
+    ///Compute the position advance_leapfrog will move the particle to, which only depends on accel_before and the
+    ///forces applied through impulse so far.
+    pub fn leapfrog_position(&self, accel_before: &V, time: D) -> V {
+        let accel = *accel_before + self.net_force() / self.inertia;
+        self.position + (self.velocity + accel * (time / (D::one() + D::one()))) * time
+    }
+
+    ///Advance particle forward in time with the symplectic kick-drift-kick leapfrog method.
+    ///
+    ///The accelerations are evaluated by the caller at the start and the end of the step. Since the drift only
+    ///depends on accel_before, the end position is known ahead of time from leapfrog_position. To step a whole
+    ///ParticleSystem, compute the accelerations of every particle, then leapfrog_position for every particle, then
+    ///the accelerations at those positions, and only then call this on every particle; the accelerations at the end
+    ///can be reused as accel_before of the next step. Any force applied through impulse is treated as constant over
+    ///the step and is consumed the same way advance consumes it.
+    pub fn advance_leapfrog(&mut self, accel_before: &V, accel_after: &V, time: D) {
+        let force = unsafe {&mut *self.force.get()};
+        let constant = *force / self.inertia;
+        let half = time / (D::one() + D::one());
+        self.velocity = self.velocity + (*accel_before + constant) * half;
+        self.position = self.position + self.velocity * time;
+        self.velocity = self.velocity + (*accel_after + constant) * half;
+        *force = V::zero();
+        self.last_accel = None;
+    }
+
+    ///Advance particle forward in time with Yoshida's fourth order symplectic method, which composes three
+    ///kick-drift-kick leapfrog substeps.
+    ///
+    ///The accel closure computes the acceleration of the particle at a position. It is called four times, since
+    ///the acceleration at the end of each substep is reused at the start of the next. The middle substep goes
+    ///backwards in time, which cancels the third order error of the outer two. Any force applied through impulse is
+    ///treated as constant over the step and is consumed the same way advance consumes it.
+    pub fn advance_yoshida4<F>(&mut self, time: D, accel: F)
+        where F: Fn(&V) -> V
+    {
+        let force = unsafe {&mut *self.force.get()};
+        let constant = *force / self.inertia;
+        let accel = |p: &V| accel(p) + constant;
+        let two = D::one() + D::one();
+        let outer = (two - two.cbrt()).recip();
+        let middle = -two.cbrt() * outer;
+        let mut current = accel(&self.position);
+        for &weight in &[outer, middle, outer] {
+            let substep = time * weight;
+            self.velocity = self.velocity + current * (substep / two);
+            self.position = self.position + self.velocity * substep;
+            current = accel(&self.position);
+            self.velocity = self.velocity + current * (substep / two);
+        }
+        *force = V::zero();
+        self.last_accel = None;
+    }
+}
+
+#[test]
+fn advance_with_accel_test() {
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 2.0);
+    let (accel, dt, steps) = (Cartesian2::new(3.0, -1.0), 0.001, 1000);
+    for _ in 0..steps {
+        p.advance_with_accel(&accel, dt);
+    }
+    let t = dt * steps as f64;
+    assert!((p.velocity - accel * t).displacement() < 1e-12);
+    //The semi-implicit kick puts the position ahead of the exact parabola by half an acceleration times t dt.
+    assert!((p.position - accel * (t * t / 2.0)).displacement() < accel.displacement() * t * dt);
+    //Forces applied through impulse are added to the acceleration and consumed.
+    p.impulse(&Cartesian2::new(2.0, 0.0));
+    p.advance_with_accel(&Cartesian2::new(0.0, 0.0), 1.0);
+    assert!((p.velocity.x - accel.x * t - 1.0).abs() < 1e-12);
+    assert_eq!(p.net_force().x, 0.0);
+}
+
+#[test]
+fn yoshida4_kepler_test() {
+    use super::super::kepler::*;
+    let (mu, a, e) = (1.0, 1.0, 0.5);
+    let accel = |position: &Cartesian2<f64>| -*position * mu / position.displacement().powi(3);
+    let energy = |p: &BasicParticle<Cartesian2<f64>, f64>| {
+        0.5 * p.velocity.displacement_squared() - mu / p.position.displacement()
+    };
+    let start = BasicParticle::new(1.0, two_body_position(mu, a, e, 0.0), two_body_velocity(mu, a, e, 0.0), 1.0);
+    let initial = energy(&start);
+    let (mut leapfrog, mut yoshida) = (start.clone(), start);
+    let dt = 0.01;
+    let (mut leapfrog_drift, mut yoshida_drift) = (0.0f64, 0.0f64);
+    //Run for about a hundred periods.
+    for _ in 0..63000 {
+        let before = accel(&leapfrog.position);
+        let after = accel(&leapfrog.leapfrog_position(&before, dt));
+        leapfrog.advance_leapfrog(&before, &after, dt);
+        yoshida.advance_yoshida4(dt, &accel);
+        leapfrog_drift = leapfrog_drift.max((energy(&leapfrog) - initial).abs());
+        yoshida_drift = yoshida_drift.max((energy(&yoshida) - initial).abs());
+    }
+    assert!(yoshida_drift * 100.0 < leapfrog_drift);
+    assert!(yoshida_drift < 1e-6);
+    //A constant force applied through impulse is integrated exactly and consumed.
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 2.0);
+    p.impulse(&Cartesian2::new(4.0, 0.0));
+    p.advance_yoshida4(1.0, |_| Cartesian2::new(0.0, 0.0));
+    assert!((p.velocity.x - 2.0).abs() < 1e-12 && (p.position.x - 1.0).abs() < 1e-12);
+    assert_eq!(p.net_force().x, 0.0);
+}
+
+#[test]
+fn checked_advance_test() {
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(1.0, 2.0), Cartesian2::new(0.5, 0.0), 0.0);
+    p.apply_force(&Cartesian2::new(1.0, 0.0));
+    assert_eq!(p.checked_advance(0.1), Err(SimError::NonFiniteVelocity));
+    assert_eq!((p.position.x, p.position.y, p.velocity.x, p.velocity.y), (1.0, 2.0, 0.5, 0.0));
+    p.inertia = 2.0;
+    assert_eq!(p.checked_advance(1.0), Ok(()));
+    assert_eq!((p.position.x, p.velocity.x), (2.0, 1.0));
+}
+
+#[test]
+fn default_test() {
+    use self::num::Zero;
+    let p: BasicParticle<Cartesian3<f64>, f64> = BasicParticle::default();
+    assert_eq!((p.quanta, p.inertia), (1.0, 1.0));
+    assert!(p.position.is_zero() && p.velocity.is_zero());
+    assert!(p.net_force().is_zero());
+    //Structs holding particles can derive Default.
+    #[derive(Default)]
+    struct Pair {
+        a: BasicParticle<Cartesian2<f64>, f64>,
+        b: BasicParticle<Cartesian2<f64>, f64>,
+    }
+    let pair = Pair::default();
+    assert_eq!((pair.a.quanta, pair.b.inertia), (1.0, 1.0));
+}
+
+#[test]
+fn approx_eq_test() {
+    let a = BasicParticle::new(1.0, Cartesian2::new(1.0, 2.0), Cartesian2::new(0.5, 0.0), 2.0);
+    let mut b = a.clone();
+    b.position.x += 1e-10;
+    b.velocity.y -= 1e-10;
+    assert!(a.approx_eq(&b, 1e-9));
+    b.inertia += 1e-8;
+    assert!(!a.approx_eq(&b, 1e-9));
+}
+
+#[test]
+fn display_test() {
+    use super::super::Cartesian2;
+    let p = BasicParticle::new(1.0, Cartesian2::new(1.0, -2.5), Cartesian2::new(0.125, 0.0), 2.0);
+    assert_eq!(format!("{}", p), "position: (1, -2.5), velocity: (0.125, 0), quanta: 1, inertia: 2");
+    assert_eq!(format!("{:.2}", p), "position: (1.00, -2.50), velocity: (0.12, 0.00), quanta: 1.00, inertia: 2.00");
+}
+
+#[test]
+fn apply_force_test() {
+    use super::super::Cartesian2;
+    use self::num::Zero;
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 2.0);
+    p.apply_force(&Cartesian2::new(1.0, 0.0));
+    p.apply_force(&Cartesian2::new(0.5, -2.0));
+    p.impulse(&Cartesian2::new(0.5, 0.0));
+    assert_eq!((p.net_force().x, p.net_force().y), (2.0, -2.0));
+    p.integrate(0.5);
+    assert_eq!((p.velocity.x, p.velocity.y), (0.5, -0.5));
+    assert_eq!((p.position.x, p.position.y), (0.25, -0.25));
+    assert!(p.net_force().is_zero());
+}
+
+#[test]
+fn semi_implicit_spring_test() {
+    use super::super::Cartesian1;
+    type P = BasicParticle<Cartesian1<f64>, f64>;
+    let anchor = P::default();
+    let energy = |p: &P| 0.5 * p.velocity.x * p.velocity.x + 0.5 * p.position.x * p.position.x;
+    let mut semi = P::new(1.0, Cartesian1::new(1.0), Cartesian1::new(0.0), 1.0);
+    let mut explicit = semi.clone();
+    for _ in 0..5000 {
+        semi.hooke_to(&anchor, 1.0);
+        semi.advance_semi_implicit(0.01);
+        explicit.hooke_to(&anchor, 1.0);
+        explicit.advance_explicit(0.01);
+    }
+    assert!((energy(&semi) - 0.5).abs() < 0.01);
+    //Explicit Euler grows the energy by a factor of 1 + dt^2 every step.
+    assert!(energy(&explicit) > 0.8);
+}
+
+#[test]
+fn clamp_speed_test() {
+    use super::super::Cartesian3;
+    let mut p = BasicParticle::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(3e9, -4e9, 1e9), 1.0);
+    let direction = p.velocity.normalized();
+    p.clamp_speed(2.5);
+    assert!((p.velocity.displacement() - 2.5).abs() <= 4.0 * f64::EPSILON);
+    assert!((p.velocity.normalized() - direction).displacement() < 1e-12);
+    //Slower particles and particles at rest are left alone.
+    p.velocity = Cartesian3::new(1.0, 1.0, 0.0);
+    p.clamp_speed(2.5);
+    assert_eq!((p.velocity.x, p.velocity.y), (1.0, 1.0));
+    p.velocity = Cartesian3::new(0.0, 0.0, 0.0);
+    p.clamp_speed(0.0);
+    assert_eq!(p.velocity.displacement(), 0.0);
+}
+
+#[test]
+fn rk4_orbit_test() {
+    use super::super::Cartesian2;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    //A unit mass in a circular orbit of radius 1 around a unit mass at the origin.
+    let start = P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 1.0), 1.0);
+    let sun = P::default();
+    let field = |p: &Cartesian2<f64>, _: &Cartesian2<f64>| -*p / p.displacement().powi(3);
+
+    let mut euler = start.clone();
+    let mut rk4 = start.clone();
+    let (mut euler_drift, mut rk4_drift) = (0.0f64, 0.0f64);
+    for _ in 0..1000 {
+        euler.gravitate_to(&sun, 1.0);
+        euler.advance(0.01);
+        rk4.advance_rk4(0.01, &field);
+        euler_drift = euler_drift.max((euler.position.displacement() - 1.0).abs());
+        rk4_drift = rk4_drift.max((rk4.position.displacement() - 1.0).abs());
+    }
+    assert!(rk4_drift * 10.0 < euler_drift);
+}
+
+#[test]
+fn verlet_oscillator_test() {
+    use super::super::Cartesian1;
+    type P = BasicParticle<Cartesian1<f64>, f64>;
+    let center = P::default();
+    let mut p = P::new(1.0, Cartesian1::new(1.0), Cartesian1::new(0.0), 1.0);
+    let energy = |p: &P| 0.5 * p.velocity.x.powi(2) + 0.5 * p.position.x.powi(2);
+    let mut worst = 0.0f64;
+    for _ in 0..10000 {
+        p.hooke_to(&center, 1.0);
+        //Measure with the retained half-kick corrected, which is what the next step does first.
+        let mut synced = p.clone();
+        if let Some(last) = p.last_accel {
+            synced.velocity = p.velocity + (-p.position - last) * 0.05;
+        }
+        worst = worst.max((energy(&synced) - 0.5).abs());
+        p.advance_verlet(0.1);
+    }
+    assert!(worst < 0.5 * 0.01);
+    //The force applied through hooke_to was consumed, so advance does not apply it again.
+    assert_eq!(p.net_force().x, 0.0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_test() {
+    use super::super::Cartesian1;
+    use serde_json;
+    type P = BasicParticle<Cartesian1<f64>, f64>;
+    let p = P::new(2.0, Cartesian1::new(-1.5), Cartesian1::new(0.25), 3.0);
+    let json = serde_json::to_string(&p).unwrap();
+    let q: P = serde_json::from_str(&json).unwrap();
+    assert_eq!((q.quanta, q.inertia, q.position.x, q.velocity.x), (2.0, 3.0, -1.5, 0.25));
+}
+
+#[test]
+fn leapfrog_impulse_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut p = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(1.0, 0.0), 2.0);
+    let accel = Cartesian2::new(0.0, 1.0);
+    p.impulse(&Cartesian2::new(2.0, 0.0));
+    let predicted = p.leapfrog_position(&accel, 0.5);
+    p.advance_leapfrog(&accel, &accel, 0.5);
+    assert_eq!((p.position.x, p.position.y), (predicted.x, predicted.y));
+    assert_eq!((p.velocity.x, p.velocity.y), (1.5, 0.5));
+    //The impulse was consumed, so the next advance does not apply it again.
+    assert_eq!(p.net_force().x, 0.0);
+}
+
+#[test]
+fn leapfrog_orbit_test() {
+    use super::super::Cartesian2;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    //An eccentric orbit around a unit mass fixed at the origin.
+    let start = P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 1.2), 1.0);
+    let sun = P::default();
+    let field = |p: &Cartesian2<f64>| -*p / p.displacement().powi(3);
+    let semi_major = |p: &P| -0.5 / (0.5 * p.velocity.displacement_squared() - 1.0 / p.position.displacement());
+    let expected = semi_major(&start);
+
+    let mut euler = start.clone();
+    let mut leapfrog = start.clone();
+    let (mut euler_drift, mut leapfrog_drift) = (0.0f64, 0.0f64);
+    let mut accel = field(&leapfrog.position);
+    for _ in 0..100000 {
+        euler.gravitate_to(&sun, 1.0);
+        euler.advance(0.001);
+        let after = field(&leapfrog.leapfrog_position(&accel, 0.001));
+        leapfrog.advance_leapfrog(&accel, &after, 0.001);
+        accel = after;
+        euler_drift = euler_drift.max((semi_major(&euler) - expected).abs());
+        leapfrog_drift = leapfrog_drift.max((semi_major(&leapfrog) - expected).abs());
+    }
+    assert!(leapfrog_drift * 100.0 < euler_drift);
+}
@@ -0,0 +1,36 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::Vector;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///ChargedParticle is a BasicParticle with an electric charge that is independent of its quanta.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "V: Deserialize<'de> + num::Zero, D: Deserialize<'de>")))]
+pub struct ChargedParticle<V, D> {
+    pub particle: BasicParticle<V, D>,
+    pub charge: D,
+}
+
+impl<V, D> ChargedParticle<V, D> {
+    pub fn new(quanta: D, charge: D, position: V, velocity: V, inertia: D) -> Self
+        where V: num::Zero
+    {
+        ChargedParticle{
+            particle: BasicParticle::new(quanta, position, velocity, inertia),
+            charge: charge,
+        }
+    }
+}
+
+impl<V, D> Charge<D> for ChargedParticle<V, D>
+    where D: Float
+{
+    fn charge(&self) -> D {
+        self.charge
+    }
+}
+
+delegate_basic_particle!(ChargedParticle where V: Vector<D>, D: Float);
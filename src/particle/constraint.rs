@@ -0,0 +1,55 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::Vector;
+
+///Keeps two particles at a fixed distance from each other like a rigid rod.
+///
+///This moves the particles directly rather than applying a force, so it should be solved after advancing.
+#[derive(Copy, Clone)]
+pub struct DistanceConstraint<D> {
+    pub rest_length: D,
+}
+
+impl<D> DistanceConstraint<D>
+    where D: Float
+{
+    pub fn new(rest_length: D) -> Self {
+        DistanceConstraint{
+            rest_length: rest_length,
+        }
+    }
+
+    ///Move both particles along the line between them until they are rest_length apart.
+    ///
+    ///The correction is split inversely to inertia, so the heavier particle moves less and the center of inertia
+    ///does not move.
+    pub fn solve<V, T1: ?Sized, T2: ?Sized>(&self, lhs: &mut T1, rhs: &mut T2)
+        where T1: Inertia<D> + PositionMut<V>, T2: Inertia<D> + PositionMut<V>, V: Vector<D>
+    {
+        let delta = rhs.position() - lhs.position();
+        let distance = delta.displacement();
+        if distance.is_normal() {
+            let correction = delta / distance * (distance - self.rest_length) / (lhs.inertia() + rhs.inertia());
+            let lhs_position = lhs.position() + correction * rhs.inertia();
+            let rhs_position = rhs.position() - correction * lhs.inertia();
+            lhs.set_position(lhs_position);
+            rhs.set_position(rhs_position);
+        }
+    }
+}
+
+#[test]
+fn distance_constraint_test() {
+    use super::super::Cartesian2;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    let mut b = P::new(1.0, Cartesian2::new(3.0, 4.0), Cartesian2::new(0.0, 0.0), 3.0);
+    let center = |a: &P, b: &P| (a.position * a.inertia + b.position * b.inertia) / (a.inertia + b.inertia);
+    let before = center(&a, &b);
+    DistanceConstraint::new(2.0).solve(&mut a, &mut b);
+    assert!(((b.position - a.position).displacement() - 2.0).abs() < 1e-12);
+    assert!((center(&a, &b) - before).displacement() < 1e-12);
+    //The heavier particle moved a third as far.
+    assert!((a.position.displacement() - 2.25).abs() < 1e-12);
+}
@@ -0,0 +1,70 @@
+///Implement the particle traits for a type that wraps a BasicParticle in a field named particle by forwarding every
+///call to it. The where clause is the bounds that every impl needs.
+macro_rules! delegate_basic_particle {
+    ($name:ident where $($bound:tt)*) => {
+        impl<V, D> Quanta<D> for $name<V, D>
+            where $($bound)*
+        {
+            fn quanta(&self) -> D {
+                self.particle.quanta()
+            }
+        }
+
+        impl<V, D> Inertia<D> for $name<V, D>
+            where $($bound)*
+        {
+            fn inertia(&self) -> D {
+                self.particle.inertia()
+            }
+        }
+
+        impl<V, D> Position<V> for $name<V, D>
+            where $($bound)*
+        {
+            fn position(&self) -> V {
+                self.particle.position()
+            }
+        }
+
+        impl<V, D> PositionMut<V> for $name<V, D>
+            where $($bound)*
+        {
+            fn set_position(&mut self, position: V) {
+                self.particle.set_position(position);
+            }
+        }
+
+        impl<V, D> Velocity<V> for $name<V, D>
+            where $($bound)*
+        {
+            fn velocity(&self) -> V {
+                self.particle.velocity()
+            }
+        }
+
+        impl<V, D> VelocityMut<V> for $name<V, D>
+            where $($bound)*
+        {
+            fn set_velocity(&mut self, velocity: V) {
+                self.particle.set_velocity(velocity);
+            }
+        }
+
+        impl<V, D> Particle<V, D> for $name<V, D>
+            where $($bound)*
+        {
+            fn impulse(&self, vec: &V) {
+                self.particle.impulse(vec);
+            }
+
+            fn advance(&mut self, time: D) {
+                self.particle.advance(time);
+            }
+        }
+
+        impl<V, D> PhysicsParticle<V, D> for $name<V, D>
+            where $($bound)*
+        {
+        }
+    }
+}
@@ -0,0 +1,20 @@
+extern crate num;
+use self::num::Float;
+
+///A medium that resists motion with a drag coefficient that can vary with position
+pub trait DragField<V, D> {
+    //Retrieve the drag coefficient at a position.
+    fn drag_at(&self, position: &V) -> D;
+}
+
+///A medium with the same drag coefficient everywhere
+#[derive(Copy, Clone)]
+pub struct UniformDragField<D>(pub D);
+
+impl<V, D> DragField<V, D> for UniformDragField<D>
+    where D: Float
+{
+    fn drag_at(&self, _: &V) -> D {
+        self.0
+    }
+}
@@ -0,0 +1,114 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::Vector;
+use std::boxed;
+
+///A PhysicsParticle whose position and velocity can be changed directly, which every particle type that implements
+///those traits is automatically.
+pub trait MovableParticle<V, D>: PhysicsParticle<V, D> + PositionMut<V> + VelocityMut<V>
+    where V: Vector<D>, D: Float
+{
+}
+
+impl<P: ?Sized, V, D> MovableParticle<V, D> for P
+    where P: PhysicsParticle<V, D> + PositionMut<V> + VelocityMut<V>, V: Vector<D>, D: Float
+{
+}
+
+///A boxed particle of any movable type, so that different particle types can be stored in one ParticleSystem.
+///
+///PhysicsParticle, PositionMut, and VelocityMut are object safe because their generic methods like gravitate_to and
+///wrap require Self: Sized. Those can't be called on a dyn MovableParticle, but the box forwards every other method,
+///so the box itself implements all three traits and has all of them. This lets system methods that move particles,
+///like recenter, work on systems of boxes. The free force functions like gravitate accept unsized particles and work
+///either way. Fixed particles don't implement PositionMut or VelocityMut, so they can't be boxed into a DynParticle.
+pub type DynParticle<V, D> = boxed::Box<dyn MovableParticle<V, D>>;
+
+impl<P: ?Sized, D> Quanta<D> for boxed::Box<P>
+    where P: Quanta<D>
+{
+    fn quanta(&self) -> D {
+        (**self).quanta()
+    }
+}
+
+impl<P: ?Sized, D> Inertia<D> for boxed::Box<P>
+    where P: Inertia<D>
+{
+    fn inertia(&self) -> D {
+        (**self).inertia()
+    }
+}
+
+impl<P: ?Sized, V> Position<V> for boxed::Box<P>
+    where P: Position<V>
+{
+    fn position(&self) -> V {
+        (**self).position()
+    }
+}
+
+impl<P: ?Sized, V> Velocity<V> for boxed::Box<P>
+    where P: Velocity<V>
+{
+    fn velocity(&self) -> V {
+        (**self).velocity()
+    }
+}
+
+impl<P: ?Sized, V> PositionMut<V> for boxed::Box<P>
+    where P: PositionMut<V>
+{
+    fn set_position(&mut self, position: V) {
+        (**self).set_position(position);
+    }
+}
+
+impl<P: ?Sized, V> VelocityMut<V> for boxed::Box<P>
+    where P: VelocityMut<V>
+{
+    fn set_velocity(&mut self, velocity: V) {
+        (**self).set_velocity(velocity);
+    }
+}
+
+impl<P: ?Sized, V, D> Particle<V, D> for boxed::Box<P>
+    where P: Particle<V, D>
+{
+    fn impulse(&self, vec: &V) {
+        (**self).impulse(vec);
+    }
+
+    fn advance(&mut self, time: D) {
+        (**self).advance(time);
+    }
+}
+
+impl<P: ?Sized, V, D> PhysicsParticle<V, D> for boxed::Box<P>
+    where P: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+}
+
+#[test]
+fn mixed_particles_test() {
+    use super::super::system::ParticleSystem;
+    type V = Cartesian2<f64>;
+    let mut system: ParticleSystem<DynParticle<V, f64>, V, f64> = ParticleSystem::new();
+    system.push(boxed::Box::new(BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0)));
+    system.push(boxed::Box::new(ChargedParticle::new(2.0, 5.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 0.0),
+        2.0)));
+    system.push(boxed::Box::new(BallParticle::new(1.0, Cartesian2::new(0.0, 1.0), Cartesian2::new(0.0, 0.0), 1.0,
+        0.1)));
+    system.step_gravity(1.0, 0.1);
+    assert!(total_momentum(system.iter()).displacement() < 1e-12);
+    let p: Vec<_> = system.iter().collect();
+    assert!(p[0].velocity().x > 0.0 && p[1].velocity().x < 0.0 && p[2].velocity().y < 0.0);
+    //Methods that need Self: Sized are still available on the box.
+    p[0].gravitate_to(&BasicParticle::new(1.0, Cartesian2::new(-1.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0), 1.0);
+    //Boxes can be moved directly, so the system can be recentered.
+    system.recenter();
+    system.reverse_velocities();
+    assert!(system.center_of_mass().unwrap().displacement() < 1e-12);
+    assert!(total_momentum(system.iter()).displacement() < 1e-12);
+}
@@ -0,0 +1,96 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::{Vector, Ball};
+
+///Fixed wraps a particle so that it can take part in interactions without ever moving, like the anchor of a pendulum.
+///
+///Forces applied to it are discarded and advancing it does nothing, but everything else is read from the inner
+///particle. It does not implement PositionMut or VelocityMut, so constraints and collisions cannot move it either.
+#[derive(Clone)]
+pub struct Fixed<P>(pub P);
+
+impl<P, D> Quanta<D> for Fixed<P>
+    where P: Quanta<D>
+{
+    fn quanta(&self) -> D {
+        self.0.quanta()
+    }
+}
+
+impl<P, D> Inertia<D> for Fixed<P>
+    where P: Inertia<D>
+{
+    fn inertia(&self) -> D {
+        self.0.inertia()
+    }
+}
+
+impl<P, D> Charge<D> for Fixed<P>
+    where P: Charge<D>
+{
+    fn charge(&self) -> D {
+        self.0.charge()
+    }
+}
+
+impl<P, D> Ball<D> for Fixed<P>
+    where P: Ball<D>
+{
+    fn radius(&self) -> D {
+        self.0.radius()
+    }
+}
+
+impl<P, D> Softening<D> for Fixed<P>
+    where P: Softening<D>
+{
+    fn softening(&self) -> D {
+        self.0.softening()
+    }
+}
+
+impl<P, V> Position<V> for Fixed<P>
+    where P: Position<V>
+{
+    fn position(&self) -> V {
+        self.0.position()
+    }
+}
+
+impl<P, V> Velocity<V> for Fixed<P>
+    where P: Velocity<V>
+{
+    fn velocity(&self) -> V {
+        self.0.velocity()
+    }
+}
+
+impl<P, V, D> Particle<V, D> for Fixed<P>
+    where P: Particle<V, D>
+{
+    fn impulse(&self, _: &V) {}
+
+    fn advance(&mut self, _: D) {}
+}
+
+impl<P, V, D> PhysicsParticle<V, D> for Fixed<P>
+    where P: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+}
+
+#[test]
+fn fixed_test() {
+    use super::super::Cartesian2;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut anchor = Fixed(P::new(1.0, Cartesian2::new(1.0, 2.0), Cartesian2::new(0.0, 0.0), 1.0));
+    let mut bob = P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    anchor.impulse(&Cartesian2::new(1e9, -1e9));
+    hooke(&anchor, &bob, 100.0);
+    anchor.advance(1.0);
+    bob.advance(1.0);
+    assert_eq!((anchor.position().x, anchor.position().y), (1.0, 2.0));
+    assert_eq!((anchor.velocity().x, anchor.velocity().y), (0.0, 0.0));
+    //The other particle still feels the anchor.
+    assert!(bob.velocity.y > 0.0);
+}
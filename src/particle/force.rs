@@ -0,0 +1,95 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::Vector;
+
+///A force model that can be chosen at runtime and stacked with others through an Integrator
+pub trait Force<P: ?Sized, V, D> {
+    //Compute the acceleration the force gives the particle.
+    fn acceleration(&self, particle: &P) -> V;
+}
+
+///A method of moving a particle forward in time under a set of forces
+pub trait Integrator<P: ?Sized, V, D> {
+    //Apply every force to the particle and advance it by dt.
+    fn step(&self, particle: &mut P, forces: &[&dyn Force<P, V, D>], dt: D);
+}
+
+///Integrates the summed acceleration of every force with the same semi-implicit Euler method as advance
+#[derive(Copy, Clone)]
+pub struct SemiImplicitEuler;
+
+impl<P: ?Sized, V, D> Integrator<P, V, D> for SemiImplicitEuler
+    where P: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+    fn step(&self, particle: &mut P, forces: &[&dyn Force<P, V, D>], dt: D) {
+        let accel = forces.iter().fold(V::zero(), |accel, force| accel + force.acceleration(particle));
+        particle.impulse(&(accel * particle.inertia()));
+        particle.advance(dt);
+    }
+}
+
+///The same attraction as gravitate_to towards a fixed center with a position and quanta
+#[derive(Copy, Clone)]
+pub struct GravityField<V, D> {
+    pub center: V,
+    pub quanta: D,
+    pub magnitude: D,
+}
+
+impl<P: ?Sized, V, D> Force<P, V, D> for GravityField<V, D>
+    where P: Particle<V, D> + Quanta<D>, V: Vector<D>, D: Float
+{
+    fn acceleration(&self, particle: &P) -> V {
+        let delta = self.center - particle.position();
+        let distance = delta.displacement();
+        if distance.is_normal() {
+            delta / distance.powi(3) * self.magnitude * particle.quanta() * self.quanta / particle.inertia()
+        } else {
+            V::zero()
+        }
+    }
+}
+
+///The same linear drag as drag with a coefficient
+#[derive(Copy, Clone)]
+pub struct DragForce<D>(pub D);
+
+impl<P: ?Sized, V, D> Force<P, V, D> for DragForce<D>
+    where P: Particle<V, D>, V: Vector<D>, D: Float
+{
+    fn acceleration(&self, particle: &P) -> V {
+        -particle.velocity() * self.0 / particle.inertia()
+    }
+}
+
+///The same constant acceleration as apply_uniform_field
+#[derive(Copy, Clone)]
+pub struct UniformField<V>(pub V);
+
+impl<P: ?Sized, V, D> Force<P, V, D> for UniformField<V>
+    where P: Particle<V, D>, V: Vector<D>, D: Float
+{
+    fn acceleration(&self, _: &P) -> V {
+        self.0
+    }
+}
+
+#[test]
+fn stacked_forces_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let center = BasicParticle::new(5.0, Cartesian2::new(3.0, 4.0), Cartesian2::new(0.0, 0.0), 1.0);
+    let gravity = GravityField{center: center.position, quanta: center.quanta, magnitude: 0.5};
+    let drag = DragForce(0.2);
+    let forces: [&dyn Force<P, _, _>; 2] = [&gravity, &drag];
+    let mut stacked = P::new(2.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(1.0, -1.0), 3.0);
+    let mut separate = stacked.clone();
+    for _ in 0..100 {
+        SemiImplicitEuler.step(&mut stacked, &forces, 0.01);
+        separate.gravitate_to(&center, 0.5);
+        separate.drag(0.2);
+        separate.advance(0.01);
+    }
+    assert!((stacked.position - separate.position).displacement() < 1e-12);
+    assert!((stacked.velocity - separate.velocity).displacement() < 1e-12);
+}
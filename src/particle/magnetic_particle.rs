@@ -0,0 +1,36 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::Vector;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///MagneticParticle is a BasicParticle with a magnetic dipole moment.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "V: Deserialize<'de> + num::Zero, D: Deserialize<'de>")))]
+pub struct MagneticParticle<V, D> {
+    pub particle: BasicParticle<V, D>,
+    pub moment: V,
+}
+
+impl<V, D> MagneticParticle<V, D> {
+    pub fn new(quanta: D, moment: V, position: V, velocity: V, inertia: D) -> Self
+        where V: num::Zero
+    {
+        MagneticParticle{
+            particle: BasicParticle::new(quanta, position, velocity, inertia),
+            moment: moment,
+        }
+    }
+}
+
+impl<V, D> MagneticMoment<V> for MagneticParticle<V, D>
+    where V: Copy
+{
+    fn moment(&self) -> V {
+        self.moment
+    }
+}
+
+delegate_basic_particle!(MagneticParticle where V: Vector<D>, D: Float);
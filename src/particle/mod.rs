@@ -1,11 +1,40 @@
 ///!Contains traits and methods for the operation of particle physics
 
 pub mod basic_particle;
+//Declared before the particle types that wrap a BasicParticle so that they can use its macro.
+#[macro_use]
+mod delegate;
+pub mod ball_particle;
+pub mod charged_particle;
+pub mod constraint;
+pub mod drag_field;
+pub mod dyn_particle;
+pub mod fixed;
+pub mod force;
+pub mod magnetic_particle;
+pub mod rigid_particle;
+pub mod softened_particle;
+#[cfg(feature = "rand")]
+pub mod random;
 pub use self::basic_particle::*;
+pub use self::ball_particle::*;
+pub use self::charged_particle::*;
+pub use self::constraint::*;
+pub use self::drag_field::*;
+pub use self::dyn_particle::*;
+pub use self::fixed::*;
+pub use self::force::*;
+pub use self::magnetic_particle::*;
+pub use self::rigid_particle::*;
+pub use self::softened_particle::*;
+#[cfg(feature = "rand")]
+pub use self::random::*;
 
 extern crate num;
 use self::num::Float;
 use super::vector::*;
+#[cfg(feature = "rand")]
+use rand::Rng;
 
 ///An object that has quanta
 pub trait Quanta<D> {
@@ -13,6 +42,24 @@ pub trait Quanta<D> {
     fn quanta(&self) -> D;
 }
 
+///An object that has electric charge
+pub trait Charge<D> {
+    //Retrieve the charge of a particle.
+    fn charge(&self) -> D;
+}
+
+///An object that has a magnetic dipole moment
+pub trait MagneticMoment<V> {
+    //Retrieve the magnetic moment of a particle.
+    fn moment(&self) -> V;
+}
+
+///An object that carries its own gravitational softening length
+pub trait Softening<D> {
+    //Retrieve the softening length of a particle.
+    fn softening(&self) -> D;
+}
+
 ///An object that has inertia
 pub trait Inertia<D> {
     //Retrieve the inertia of a physics particle.
@@ -31,6 +78,35 @@ pub trait Velocity<V> {
     fn velocity(&self) -> V;
 }
 
+///An object whose location can be changed directly
+pub trait PositionMut<V>: Position<V> {
+    //Move the particle to a new position.
+    fn set_position(&mut self, position: V);
+
+    ///Wrap the particle back inside of a toroidal space without changing its velocity.
+    fn wrap<T: ?Sized>(&mut self, space: &T)
+        where T: Toroid<V>, Self: Sized
+    {
+        let position = space.wrap_position(self.position());
+        self.set_position(position);
+    }
+}
+
+///An object whose velocity can be changed directly
+pub trait VelocityMut<V>: Velocity<V> {
+    //Change the velocity of the particle.
+    fn set_velocity(&mut self, velocity: V);
+
+    ///Keep the particle inside of a walled space by bouncing it off of the walls with a coefficient of restitution.
+    fn reflect<T: ?Sized, D>(&mut self, space: &T, restitution: D)
+        where Self: PositionMut<V> + Sized, T: Walled<V, D>
+    {
+        let (position, velocity) = space.reflect(self.position(), self.velocity(), restitution);
+        self.set_position(position);
+        self.set_velocity(velocity);
+    }
+}
+
 ///An object that has a simple particle motion interface
 pub trait Particle<V, D>: Position<V> + Velocity<V> + Inertia<D> {
     ///Apply force to particle, but it isn't moved forward in time until advance is called.
@@ -40,7 +116,36 @@ pub trait Particle<V, D>: Position<V> + Velocity<V> + Inertia<D> {
     fn advance(&mut self, time: D);
 }
 
+///A vector that can compute angular momentum, which is a scalar in 2d and a vector in 3d.
+pub trait AngularMomentum<D> {
+    type Output;
+    ///Compute the angular momentum of a momentum with an offset from the center of rotation.
+    fn angular_momentum(offset: &Self, momentum: &Self) -> Self::Output;
+}
+
+impl<D> AngularMomentum<D> for Cartesian2<D>
+    where D: Float
+{
+    type Output = D;
+    fn angular_momentum(offset: &Self, momentum: &Self) -> D {
+        //This is the z component of the cross product if both vectors were in the xy plane of 3d space.
+        offset.x * momentum.y - offset.y * momentum.x
+    }
+}
+
+impl<D> AngularMomentum<D> for Cartesian3<D>
+    where D: Float
+{
+    type Output = Self;
+    fn angular_momentum(offset: &Self, momentum: &Self) -> Self {
+        Self::cross(offset, momentum)
+    }
+}
+
 ///Any particle that implements the necessary traits gains access to all of the capabilities of PhysicsParticle.
+///
+///Methods that are generic or need other traits require Self: Sized so that the trait stays object safe, which
+///lets DynParticle store different particle types together.
 pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
     where V: Vector<D>, D: Float
 {
@@ -50,15 +155,76 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
         BasicParticle::new(self.quanta(), self.position(), self.velocity(), self.inertia())
     }
 
+    ///Change the velocity by j / inertia right away, like a collision or a short burst of thrust.
+    ///
+    ///Despite its name, impulse adds a force that only changes the velocity once advance integrates it over a time
+    ///step, while this applies the already integrated impulse j and ignores any time step.
+    fn apply_impulse(&mut self, j: &V)
+        where Self: VelocityMut<V> + Sized
+    {
+        let velocity = self.velocity() + *j / self.inertia();
+        self.set_velocity(velocity);
+    }
+
+    ///Compute the kinetic energy of the particle from its inertia and velocity.
+    fn kinetic_energy(&self) -> D {
+        (D::one() + D::one()).recip() * self.inertia() * self.velocity().displacement_squared()
+    }
+
+    ///Compute the momentum of the particle from its inertia and velocity.
+    fn momentum(&self) -> V {
+        self.velocity() * self.inertia()
+    }
+
+    ///Compute the angular momentum of the particle around a center of rotation.
+    fn angular_momentum_about(&self, center: &V) -> <V as AngularMomentum<D>>::Output
+        where V: AngularMomentum<D>
+    {
+        V::angular_momentum(&(self.position() - *center), &self.momentum())
+    }
+
     ///Apply drag forces to a particle.
     fn drag(&self, magnitude: D) {
         let force = -self.velocity() * magnitude;
         self.impulse(&force);
     }
 
+    ///Apply linear drag forces with the coefficient of field at the position of the particle.
+    fn apply_drag_field<F: ?Sized>(&self, field: &F)
+        where F: DragField<V, D>, Self: Sized
+    {
+        self.drag(field.drag_at(&self.position()));
+    }
+
+    ///Apply a random acceleration for the next advance by dt, where every component is normally distributed with a
+    ///variance of temperature / dt.
+    ///
+    ///Once advanced, every velocity component has changed by a normal amount with a variance of temperature * dt, so
+    ///the velocity diffuses at the same rate whatever the time step is. Together with drag of magnitude gamma this
+    ///gives Langevin dynamics, where every velocity component settles at a variance of
+    ///temperature * inertia / (2 * gamma).
+    #[cfg(feature = "rand")]
+    fn brownian_kick<R: ?Sized>(&self, temperature: D, dt: D, rng: &mut R)
+        where R: Rng, Self: Sized
+    {
+        let scale = (temperature / dt).sqrt();
+        let components: Vec<D> = (0..V::dimensions()).map(|_| random::standard_normal::<D, R>(rng) * scale).collect();
+        self.impulse(&(V::from_slice(&components) * self.inertia()));
+    }
+
+    ///Apply drag forces proportional to the square of the speed of a particle, like aerodynamic drag at high speed.
+    fn drag_quadratic(&self, magnitude: D) {
+        let velocity = self.velocity();
+        let force = -velocity * velocity.displacement() * magnitude;
+        self.impulse(&force);
+    }
+
     ///Apply proper attraction to a single physics particle towards a location and with a magnitude.
+    ///
+    ///The force is scaled by the product of both quanta, so it attracts when the product is positive and repels when
+    ///it is negative, like a center with negative quanta.
     fn gravitate_to<T: ?Sized>(&self, center: &T, magnitude: D)
-        where T: Quanta<D> + Position<V>
+        where T: Quanta<D> + Position<V>, Self: Sized
     {
         //Create delta vector from the particle to the center of attraction.
         let delta = center.position() - self.position();
@@ -69,19 +235,35 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
         }
     }
 
+    ///Same as gravitate_to, but the center is a real particle that recoils with the opposite force.
+    ///
+    ///This is gravitate with the satellite first, for when one main body should feel the pull of each satellite.
+    fn gravitate_to_mut_center<T: ?Sized>(&self, center: &T, magnitude: D)
+        where T: PhysicsParticle<V, D>, Self: Sized
+    {
+        gravitate(self, center, magnitude);
+    }
+
     ///This works the same as gravitate_radius_squared and gravitate_to.
     fn gravitate_radius_to<T: ?Sized>(&self, center: &T, magnitude: D)
-        where T: Quanta<D> + Position<V> + Ball<D>
+        where T: Quanta<D> + Position<V> + Ball<D>, Self: Sized
+    {
+        self.gravitate_radius_value_to(center, center.radius(), magnitude);
+    }
+
+    ///Same as gravitate_radius_to, but the radius of the center is passed in, so the center doesn't have to be a Ball.
+    fn gravitate_radius_value_to<T: ?Sized>(&self, center: &T, radius: D, magnitude: D)
+        where T: Quanta<D> + Position<V>, Self: Sized
     {
         //Create delta vector from the particle to the center of attraction.
         let delta = center.position() - self.position();
         let distance_squared = delta.displacement_squared();
         if distance_squared.is_normal() {
             let force = delta * magnitude * self.quanta() * center.quanta() /
-                if distance_squared > center.radius().powi(2) {
+                if distance_squared > radius.powi(2) {
                     distance_squared.sqrt().powi(3)
                 } else {
-                    center.radius().powi(2)
+                    radius.powi(2)
                 };
             self.impulse(&force);
         }
@@ -89,7 +271,7 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
 
     ///Apply spring forces between one particle and a virtual particle that is unaffected.
     fn hooke_to<T: ?Sized>(&self, center: &T, magnitude: D)
-        where T: Quanta<D> + Position<V>
+        where T: Quanta<D> + Position<V>, Self: Sized
     {
         let delta = center.position() - self.position();
         let force = delta * magnitude * self.quanta() * center.quanta();
@@ -98,7 +280,7 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
 
     ///Apply spring forces between one particle and a virtual particle that is unaffected.
     fn hooke_equilibrium_to<T: ?Sized>(&self, center: &T, equilibrium: D, magnitude: D)
-        where T: Quanta<D> + Position<V>
+        where T: Quanta<D> + Position<V>, Self: Sized
     {
         let delta = center.position() - self.position();
         let displace = delta.displacement();
@@ -109,6 +291,13 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
         }
     }
 
+    ///Accelerate a particle by field regardless of its quanta or inertia, like gravity near the surface of a planet.
+    ///
+    ///Unlike lorentz_field, which depends on the quanta and inertia of the particle, the field is the acceleration.
+    fn apply_uniform_field(&self, field: &V) {
+        self.impulse(&(*field * self.inertia()));
+    }
+
     ///Apply lorentz force to a particle in a field given by a vector with the magnitude and direction of the field.
     fn lorentz_field(&self, field: &V)
         where V: CrossVector
@@ -119,7 +308,7 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
 
     ///Apply the lorentz force on a virtual particle that is unaffected.
     fn lorentz_to<T: ?Sized>(&self, center: &T, magnitude: D)
-        where V: CrossVector, T: Quanta<D> + Position<V> + Velocity<V>
+        where V: CrossVector, T: Quanta<D> + Position<V> + Velocity<V>, Self: Sized
     {
         let delta = center.position() - self.position();
         let distance_squared = delta.displacement_squared();
@@ -133,7 +322,7 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
 
     ///Apply the lorentz force on a virtual particle that is unaffected.
     fn lorentz_radius_to<T: ?Sized>(&self, center: &T, magnitude: D)
-        where V: CrossVector, T: Quanta<D> + Position<V> + Velocity<V> + Ball<D>
+        where V: CrossVector, T: Quanta<D> + Position<V> + Velocity<V> + Ball<D>, Self: Sized
     {
         let delta = center.position() - self.position();
         let distance_squared = delta.displacement_squared();
@@ -151,6 +340,473 @@ pub trait PhysicsParticle<V, D>: Particle<V, D> + Quanta<D> + Inertia<D>
     }
 }
 
+#[test]
+fn drag_field_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    //Thick fluid for x < 0 and thin fluid for x >= 0.
+    struct Step;
+    impl DragField<Cartesian2<f64>, f64> for Step {
+        fn drag_at(&self, position: &Cartesian2<f64>) -> f64 {
+            if position.x < 0.0 {2.0} else {0.5}
+        }
+    }
+    let mut thick = P::new(1.0, Cartesian2::new(-5.0, 0.0), Cartesian2::new(0.0, 1.0), 1.0);
+    let mut thin = P::new(1.0, Cartesian2::new(5.0, 0.0), Cartesian2::new(0.0, 1.0), 1.0);
+    for _ in 0..10 {
+        thick.apply_drag_field(&Step);
+        thin.apply_drag_field(&Step);
+        thick.advance(0.1);
+        thin.advance(0.1);
+    }
+    assert!(thick.velocity.y < thin.velocity.y && thin.velocity.y < 1.0);
+
+    let mut uniform = P::new(1.0, Cartesian2::new(3.0, 0.0), Cartesian2::new(2.0, 0.0), 1.0);
+    uniform.apply_drag_field(&UniformDragField(0.5));
+    uniform.advance(1.0);
+    assert_eq!(uniform.velocity.x, 1.0);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn brownian_kick_test() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    let mut rng = StdRng::seed_from_u64(7);
+    let statistics = |temperature: f64, dt: f64, rng: &mut StdRng| {
+        let n = 20000;
+        let mut p = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 2.0);
+        let (mut sum, mut squares) = (0.0, 0.0);
+        for _ in 0..n {
+            p.velocity = Cartesian2::new(0.0, 0.0);
+            p.brownian_kick(temperature, dt, rng);
+            p.advance(dt);
+            sum += p.velocity.x + p.velocity.y;
+            squares += p.velocity.displacement_squared();
+        }
+        (sum / (2 * n) as f64, squares / (2 * n) as f64)
+    };
+    let (cold_mean, cold_variance) = statistics(1.0, 1.0, &mut rng);
+    let (hot_mean, hot_variance) = statistics(4.0, 1.0, &mut rng);
+    assert!(cold_mean.abs() < 0.03 && hot_mean.abs() < 0.06);
+    assert!((cold_variance - 1.0).abs() < 0.05);
+    assert!((hot_variance / cold_variance - 4.0).abs() < 0.3);
+    //The variance of one step is proportional to its length.
+    let (_, short_variance) = statistics(1.0, 0.25, &mut rng);
+    assert!((short_variance / cold_variance - 0.25).abs() < 0.02);
+
+    //With drag the velocity settles at a variance of temperature * inertia / (2 * gamma).
+    let (temperature, gamma, dt) = (1.0, 2.0, 0.01);
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 2.0);
+    let mut squares = 0.0;
+    let n = 200000;
+    for _ in 0..n {
+        p.drag(gamma);
+        p.brownian_kick(temperature, dt, &mut rng);
+        p.advance(dt);
+        squares += p.velocity.displacement_squared();
+    }
+    assert!((squares / (2 * n) as f64 - temperature * 2.0 / (2.0 * gamma)).abs() < 0.1);
+}
+
+#[test]
+fn uniform_field_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let gravity = Cartesian2::new(0.0, -9.8);
+    //The trajectory must not depend on the inertia.
+    for &inertia in &[0.5, 30.0] {
+        let mut p = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(2.0, 5.0), inertia);
+        let dt = 0.001;
+        for step in 1..1001 {
+            p.apply_uniform_field(&gravity);
+            p.advance(dt);
+            //Semi-implicit Euler follows the parabola with the time of the quadratic term shifted by half a step.
+            let t = step as f64 * dt;
+            let expected = 5.0 * t - 0.5 * 9.8 * t * (t + dt);
+            assert!((p.position.x - 2.0 * t).abs() < 1e-9);
+            assert!((p.position.y - expected).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn gravitate_to_sign_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let velocity = |quanta: f64, center_quanta: f64| {
+        let mut p = P::new(quanta, Cartesian2::new(2.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+        p.gravitate_to(&P::new(center_quanta, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0), 1.0);
+        p.advance(1.0);
+        p.velocity
+    };
+    //A positive product of quanta pulls the particle towards the center at the origin.
+    assert_eq!(velocity(1.0, 4.0).x, -1.0);
+    assert_eq!(velocity(-1.0, -4.0).x, -1.0);
+    //A negative product pushes it away with the same strength.
+    assert_eq!(velocity(1.0, -4.0).x, 1.0);
+    assert_eq!(velocity(-1.0, 4.0).x, 1.0);
+    assert_eq!(velocity(1.0, -4.0).y, 0.0);
+}
+
+#[test]
+fn gravitate_radius_value_to_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let ball = BallParticle::new(3.0, Cartesian2::new(1.0, 1.0), Cartesian2::new(0.0, 0.0), 1.0, 2.0);
+    let plain = P::new(3.0, Cartesian2::new(1.0, 1.0), Cartesian2::new(0.0, 0.0), 1.0);
+    //One particle is inside of the radius and the other is outside of it.
+    for &x in &[2.0, 4.0] {
+        let mut a = P::new(1.0, Cartesian2::new(x, 1.0), Cartesian2::new(0.0, 0.0), 1.0);
+        let mut b = a.clone();
+        a.gravitate_radius_to(&ball, 1.0);
+        b.gravitate_radius_value_to(&plain, 2.0, 1.0);
+        a.advance(1.0);
+        b.advance(1.0);
+        assert_eq!((a.velocity.x, a.velocity.y), (b.velocity.x, b.velocity.y));
+        assert!(a.velocity.x < 0.0);
+    }
+}
+
+#[test]
+fn apply_impulse_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut p = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(1.0, -1.0), 4.0);
+    let delta_v = Cartesian2::new(0.5, 2.0);
+    p.apply_impulse(&(delta_v * 4.0));
+    assert_eq!((p.velocity.x, p.velocity.y), (1.5, 1.0));
+    //Nothing was accumulated, so advancing only moves the particle.
+    p.advance(2.0);
+    assert_eq!((p.velocity.x, p.velocity.y), (1.5, 1.0));
+    assert_eq!((p.position.x, p.position.y), (3.0, 2.0));
+}
+
+#[test]
+fn drag_quadratic_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let gravity = Cartesian2::new(0.0, -4.0);
+    let mut linear = P::default();
+    let mut quadratic = P::default();
+    //Drag on a particle at rest must not produce NaN.
+    quadratic.drag_quadratic(1.0);
+    for _ in 0..10000 {
+        linear.impulse(&gravity);
+        linear.drag(1.0);
+        linear.advance(0.001);
+        quadratic.impulse(&gravity);
+        quadratic.drag_quadratic(1.0);
+        quadratic.advance(0.001);
+    }
+    assert!(quadratic.velocity.x == 0.0 && linear.velocity.x == 0.0);
+    //Terminal velocity is the magnitude of gravity for linear drag and its square root for quadratic drag.
+    assert!((linear.velocity.y + 4.0).abs() < 1e-3);
+    assert!((quadratic.velocity.y + 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn wrap_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let space = Box::from_corners(Cartesian2::new(0.0, 0.0), Cartesian2::new(10.0, 4.0));
+    let mut p = P::new(1.0, Cartesian2::new(10.5, 2.0), Cartesian2::new(1.0, -1.0), 1.0);
+    p.wrap(&space);
+    assert!((p.position.x - 0.5).abs() < 1e-12 && p.position.y == 2.0);
+    assert_eq!((p.velocity.x, p.velocity.y), (1.0, -1.0));
+    //Particles near opposite edges are close through the boundary.
+    let delta = space.wrap_delta(Cartesian2::new(9.0, 0.0) - Cartesian2::new(1.0, 0.0));
+    assert!((delta.x + 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn reflect_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let space = Box::from_corners(Cartesian2::new(0.0, 0.0), Cartesian2::new(10.0, 4.0));
+    let mut p = P::new(1.0, Cartesian2::new(9.5, 2.0), Cartesian2::new(1.0, 0.5), 1.0);
+    p.advance(1.0);
+    p.reflect(&space, 0.8);
+    assert_eq!(p.position.x, 10.0);
+    assert!((p.velocity.x + 0.8).abs() < 1e-12 && p.velocity.y == 0.5);
+    //The particle is now moving back in, so it is not reflected again.
+    p.reflect(&space, 0.8);
+    assert!((p.velocity.x + 0.8).abs() < 1e-12);
+}
+
+///Sum the kinetic energy of every particle in an iterator.
+pub fn total_kinetic_energy<'a, I, P: ?Sized, V, D>(particles: I) -> D
+    where I: IntoIterator<Item=&'a P>, P: PhysicsParticle<V, D> + 'a, V: Vector<D>, D: Float
+{
+    particles.into_iter().fold(D::zero(), |energy, p| energy + p.kinetic_energy())
+}
+
+///Sum the momentum of every particle in an iterator.
+pub fn total_momentum<'a, I, P: ?Sized, V, D>(particles: I) -> V
+    where I: IntoIterator<Item=&'a P>, P: PhysicsParticle<V, D> + 'a, V: Vector<D>, D: Float
+{
+    particles.into_iter().fold(V::zero(), |momentum, p| momentum + p.momentum())
+}
+
+#[test]
+fn momentum_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut particles = [
+        P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(1.0, 0.0, 0.5), 2.0),
+        P::new(3.0, Cartesian3::new(1.0, 2.0, 0.0), Cartesian3::new(0.0, -2.0, 0.0), 0.5),
+    ];
+    let before = total_momentum(&particles);
+    assert!((before - Cartesian3::new(2.0, -1.0, 1.0)).displacement() < 1e-12);
+    gravitate(&particles[0], &particles[1], 1.0);
+    for p in &mut particles {
+        p.advance(0.1);
+    }
+    assert!((total_momentum(&particles) - before).displacement() < 1e-12);
+}
+
+#[test]
+fn angular_momentum_test() {
+    let sun = BasicParticle::<Cartesian2<f64>, f64>::default();
+    let mut p = BasicParticle::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 1.0), 2.0);
+    let start = p.angular_momentum_about(&sun.position);
+    assert_eq!(start, 2.0);
+    for _ in 0..100 {
+        p.gravitate_to(&sun, 2.0);
+        p.advance(0.01);
+        assert!((p.angular_momentum_about(&sun.position) - start).abs() < 1e-12);
+    }
+
+    let center = Cartesian3::new(0.0, 0.0, 1.0);
+    let sun = BasicParticle::new(1.0, center, Cartesian3::new(0.0, 0.0, 0.0), 1.0);
+    let mut p = BasicParticle::new(1.0, Cartesian3::new(1.0, 0.0, 1.0), Cartesian3::new(0.0, 0.6, 0.8), 1.0);
+    let start = p.angular_momentum_about(&center);
+    assert!((start - Cartesian3::new(0.0, -0.8, 0.6)).displacement() < 1e-12);
+    for _ in 0..100 {
+        p.gravitate_to(&sun, 1.0);
+        p.advance(0.01);
+        assert!((p.angular_momentum_about(&center) - start).displacement() < 1e-12);
+    }
+}
+
+#[test]
+fn kinetic_energy_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let particles = [
+        P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(3.0, 4.0), 2.0),
+        P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, -2.0), 0.5),
+    ];
+    assert_eq!(particles[0].kinetic_energy(), 25.0);
+    assert_eq!(total_kinetic_energy(&particles), 26.0);
+}
+
+///Apply electrostatic forces between two charged particles, which repel when the charges have the same sign.
+pub fn coulomb<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
+    where T1: Particle<V, D> + Charge<D>, T2: Particle<V, D> + Charge<D>, V: Vector<D>, D: Float
+{
+    let delta = rhs.position() - lhs.position();
+    let distance = delta.displacement();
+    if distance.is_normal() {
+        //Unlike gravitation, a positive product of charges pushes lhs away from rhs.
+        let force = delta * magnitude / distance.powi(3) * lhs.charge() * rhs.charge();
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+///Same as coulomb, but uses a comp_delta closure to compute the delta from the first to second parameter
+pub fn coulomb_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D, comp_delta: F)
+    where T1: Particle<V, D> + Charge<D>, T2: Particle<V, D> + Charge<D>, V: Vector<D>, D: Float,
+    F: FnOnce((V, V)) -> V
+{
+    //Create delta vector between the two positions.
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let distance = delta.displacement();
+    if distance.is_normal() {
+        let force = delta * magnitude / distance.powi(3) * lhs.charge() * rhs.charge();
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+#[test]
+fn coulomb_test() {
+    type P = ChargedParticle<Cartesian1<f64>, f64>;
+    let mut a = P::new(1.0, 1.0, Cartesian1::new(0.0), Cartesian1::new(0.0), 1.0);
+    let mut b = P::new(1.0, 2.0, Cartesian1::new(1.0), Cartesian1::new(0.0), 1.0);
+    coulomb(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert_eq!((a.velocity().x, b.velocity().x), (-2.0, 2.0));
+
+    let mut a = P::new(1.0, 1.0, Cartesian1::new(0.0), Cartesian1::new(0.0), 1.0);
+    let mut b = P::new(1.0, -2.0, Cartesian1::new(1.0), Cartesian1::new(0.0), 1.0);
+    coulomb(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert_eq!((a.velocity().x, b.velocity().x), (2.0, -2.0));
+}
+
+///Apply the Lennard-Jones 12-6 force between two particles, where epsilon is the depth of the potential well and
+///sigma is the separation at which the potential crosses zero.
+///
+///The particles repel closer than 2^(1/6)*sigma and attract further away. Nothing happens at zero separation.
+pub fn lennard_jones<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, epsilon: D, sigma: D)
+    where T1: Particle<V, D>, T2: Particle<V, D>, V: Vector<D>, D: Float
+{
+    lennard_jones_delta(lhs, rhs, epsilon, sigma, |(l, r)| r - l);
+}
+
+///Same as lennard_jones, but uses a comp_delta closure to compute the delta from the first to second parameter
+pub fn lennard_jones_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, epsilon: D, sigma: D, comp_delta: F)
+    where T1: Particle<V, D>, T2: Particle<V, D>, V: Vector<D>, D: Float,
+    F: FnOnce((V, V)) -> V
+{
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let distance_squared = delta.displacement_squared();
+    if distance_squared.is_normal() {
+        let six = (sigma * sigma / distance_squared).powi(3);
+        //The magnitude is 24 epsilon (2 (sigma/r)^12 - (sigma/r)^6) / r, which is divided by r again to scale delta.
+        let scale = num::cast::<_, D>(24).unwrap() * epsilon * (six * six + six * six - six) / distance_squared;
+        let force = delta * scale;
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+#[test]
+fn lennard_jones_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let sigma = 0.5;
+    let push = |distance: f64| {
+        let mut a = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+        let mut b = P::new(1.0, Cartesian2::new(distance, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+        lennard_jones(&a, &b, 2.0, sigma);
+        a.advance(1.0);
+        b.advance(1.0);
+        assert_eq!(a.velocity().x, -b.velocity().x);
+        b.velocity().x
+    };
+    assert!(push(2.0f64.powf(1.0 / 6.0) * sigma).abs() < 1e-12);
+    assert!(push(sigma) > 0.0);
+    assert!(push(2.0 * sigma) < 0.0);
+    assert_eq!(push(0.0), 0.0);
+}
+
+///Resolve a collision between two balls with a coefficient of restitution in [0, 1].
+///
+///If the balls overlap, they are pushed apart along the line between their centers so that they are just touching,
+///with the lighter ball moving further. If they are also approaching each other, the component of their velocities
+///along that line is exchanged as in a one dimensional collision, where a restitution of 1 loses no kinetic energy
+///and a restitution of 0 leaves them moving together.
+pub fn resolve_collision<V, D, T1: ?Sized, T2: ?Sized>(lhs: &mut T1, rhs: &mut T2, restitution: D)
+    where T1: PhysicsParticle<V, D> + Ball<D> + PositionMut<V> + VelocityMut<V>,
+    T2: PhysicsParticle<V, D> + Ball<D> + PositionMut<V> + VelocityMut<V>, V: Vector<D>, D: Float
+{
+    let delta = rhs.position() - lhs.position();
+    let distance = delta.displacement();
+    let overlap = lhs.radius() + rhs.radius() - distance;
+    if overlap > D::zero() && distance.is_normal() {
+        let normal = delta / distance;
+        let (lhs_inertia, rhs_inertia) = (lhs.inertia(), rhs.inertia());
+        let net_inertia = lhs_inertia + rhs_inertia;
+
+        //Separate the particles so that the center of inertia does not move.
+        let lhs_position = lhs.position() - normal * (overlap * rhs_inertia / net_inertia);
+        let rhs_position = rhs.position() + normal * (overlap * lhs_inertia / net_inertia);
+        lhs.set_position(lhs_position);
+        rhs.set_position(rhs_position);
+
+        //Only exchange momentum if the particles are moving into each other.
+        let approach = V::dot(&(lhs.velocity() - rhs.velocity()), &normal);
+        if approach > D::zero() {
+            let impulse = normal * ((D::one() + restitution) * approach * lhs_inertia * rhs_inertia / net_inertia);
+            let lhs_velocity = lhs.velocity() - impulse / lhs_inertia;
+            let rhs_velocity = rhs.velocity() + impulse / rhs_inertia;
+            lhs.set_velocity(lhs_velocity);
+            rhs.set_velocity(rhs_velocity);
+        }
+    }
+}
+
+///Resolve a perfectly elastic collision between two balls; see resolve_collision.
+pub fn resolve_elastic_collision<V, D, T1: ?Sized, T2: ?Sized>(lhs: &mut T1, rhs: &mut T2)
+    where T1: PhysicsParticle<V, D> + Ball<D> + PositionMut<V> + VelocityMut<V>,
+    T2: PhysicsParticle<V, D> + Ball<D> + PositionMut<V> + VelocityMut<V>, V: Vector<D>, D: Float
+{
+    resolve_collision(lhs, rhs, D::one());
+}
+
+#[test]
+fn elastic_collision_test() {
+    type P = BallParticle<Cartesian2<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(1.0, 0.0), 1.0, 1.0);
+    let mut b = P::new(1.0, Cartesian2::new(1.5, 0.0), Cartesian2::new(-1.0, 0.0), 3.0, 1.0);
+    let momentum = a.velocity().x * a.inertia() + b.velocity().x * b.inertia();
+    let energy = a.kinetic_energy() + b.kinetic_energy();
+    resolve_elastic_collision(&mut a, &mut b);
+    assert!((a.velocity().x * a.inertia() + b.velocity().x * b.inertia() - momentum).abs() < 1e-12);
+    assert!((a.kinetic_energy() + b.kinetic_energy() - energy).abs() < 1e-12);
+    assert!((a.velocity().x + 2.0).abs() < 1e-12 && b.velocity().x.abs() < 1e-12);
+    //The particles must now be touching and not overlapping.
+    assert!(((b.position() - a.position()).displacement() - 2.0).abs() < 1e-12);
+
+    //Separated particles do not collide again.
+    let (va, vb) = (a.velocity(), b.velocity());
+    resolve_elastic_collision(&mut a, &mut b);
+    assert_eq!((a.velocity().x, b.velocity().x), (va.x, vb.x));
+}
+
+#[test]
+fn inelastic_collision_test() {
+    type P = BallParticle<Cartesian2<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(1.0, 0.0), 1.0, 1.0);
+    let mut b = P::new(1.0, Cartesian2::new(1.5, 0.0), Cartesian2::new(-1.0, 0.0), 1.0, 1.0);
+    resolve_collision(&mut a, &mut b, 0.0);
+    //With no restitution the particles stick together.
+    assert!((a.velocity().x - b.velocity().x).abs() < 1e-12);
+    assert!((a.velocity().x + b.velocity().x).abs() < 1e-12);
+}
+
+///Compute the gravitational potential energy between two particles, which is the energy gravitate releases as they
+///approach. Particles at the same position have no potential since gravitate applies no force to them.
+pub fn gravitational_potential<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D) -> D
+    where T1: Quanta<D> + Position<V>, T2: Quanta<D> + Position<V>, V: Vector<D>, D: Float
+{
+    let distance = (rhs.position() - lhs.position()).displacement();
+    if distance.is_normal() {
+        -magnitude * lhs.quanta() * rhs.quanta() / distance
+    } else {
+        D::zero()
+    }
+}
+
+///Compute the potential energy stored in the spring that hooke applies between two particles.
+pub fn hooke_potential<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D) -> D
+    where T1: Quanta<D> + Position<V>, T2: Quanta<D> + Position<V>, V: Vector<D>, D: Float
+{
+    let distance_squared = (rhs.position() - lhs.position()).displacement_squared();
+    (D::one() + D::one()).recip() * magnitude * lhs.quanta() * rhs.quanta() * distance_squared
+}
+
+#[test]
+fn potential_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian2::new(-0.5, 0.0), Cartesian2::new(0.0, -0.6), 1.0);
+    let mut b = P::new(1.0, Cartesian2::new(0.5, 0.0), Cartesian2::new(0.0, 0.6), 1.0);
+    assert_eq!(hooke_potential(&a, &b, 2.0), 1.0);
+    let energy = |a: &P, b: &P| a.kinetic_energy() + b.kinetic_energy() + gravitational_potential(a, b, 1.0);
+    let start = energy(&a, &b);
+    assert!((start + 0.64).abs() < 1e-12);
+
+    //Gravitational acceleration of lhs towards rhs at the given positions.
+    let pull = |lhs: &Cartesian2<f64>, rhs: &Cartesian2<f64>| {
+        let delta = *rhs - *lhs;
+        delta / delta.displacement().powi(3)
+    };
+    let (mut accel_a, mut accel_b) = (pull(&a.position, &b.position), pull(&b.position, &a.position));
+    for _ in 0..10000 {
+        let (next_a, next_b) = (a.leapfrog_position(&accel_a, 0.001), b.leapfrog_position(&accel_b, 0.001));
+        let (after_a, after_b) = (pull(&next_a, &next_b), pull(&next_b, &next_a));
+        a.advance_leapfrog(&accel_a, &after_a, 0.001);
+        b.advance_leapfrog(&accel_b, &after_b, 0.001);
+        accel_a = after_a;
+        accel_b = after_b;
+        assert!((energy(&a, &b) - start).abs() < 1e-5);
+    }
+}
+
 ///Apply proper attraction between two physics particles based on their quanta and position.
 pub fn gravitate<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
     where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float
@@ -195,6 +851,154 @@ pub fn gravitate_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magn
     }
 }
 
+///Compute how much more gravitate would accelerate lhs towards rhs if lhs were moved by offset, which is the tidal
+///acceleration across lhs.
+///
+///Offsets along the line to rhs are stretched away from the center of lhs while perpendicular offsets are squeezed
+///towards it. Positions that coincide with rhs feel no acceleration, the same as in gravitate.
+pub fn tidal_acceleration<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, offset: &V, magnitude: D) -> V
+    where T1: Particle<V, D> + Quanta<D>, T2: Quanta<D> + Position<V>, V: Vector<D>, D: Float
+{
+    let accel = |position: V| {
+        let delta = rhs.position() - position;
+        let distance = delta.displacement();
+        if distance.is_normal() {
+            delta / distance.powi(3)
+        } else {
+            V::zero()
+        }
+    };
+    (accel(lhs.position() + *offset) - accel(lhs.position())) *
+        magnitude * lhs.quanta() * rhs.quanta() / lhs.inertia()
+}
+
+#[test]
+fn tidal_acceleration_test() {
+    use self::num::Zero;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let lhs = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    let rhs = P::new(1000.0, Cartesian2::new(10.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    //The tidal acceleration is about 2GMd/r^3 along the line and -GMd/r^3 across it.
+    let d = 0.01;
+    let near = tidal_acceleration(&lhs, &rhs, &Cartesian2::new(d, 0.0), 1.0);
+    let far = tidal_acceleration(&lhs, &rhs, &Cartesian2::new(-d, 0.0), 1.0);
+    let side = tidal_acceleration(&lhs, &rhs, &Cartesian2::new(0.0, d), 1.0);
+    assert!(near.x > 0.0 && far.x < 0.0);
+    assert!((near.x - 2.0 * d).abs() < 1e-4 && (far.x + 2.0 * d).abs() < 1e-4);
+    assert!(side.y < 0.0 && (side.y + d).abs() < 1e-4);
+    assert!(tidal_acceleration(&lhs, &rhs, &Cartesian2::new(0.0, 0.0), 1.0).is_zero());
+}
+
+///Apply attraction between two physics particles with Plummer softening, which replaces the distance cubed with
+///(distance^2 + softening^2)^(3/2) so that the force stays finite as the particles approach each other.
+pub fn gravitate_softened<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, softening: D, magnitude: D)
+    where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+    let delta = rhs.position() - lhs.position();
+    let softened = (delta.displacement_squared() + softening.powi(2)).sqrt().powi(3);
+    if softened.is_normal() {
+        let force = delta * magnitude / softened * lhs.quanta() * rhs.quanta();
+        lhs.impulse(&force);
+        rhs.impulse(&-force);
+    }
+}
+
+///Same as gravitate_softened, but uses a special comp_delta closure to compute the delta between the particles
+pub fn gravitate_softened_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, softening: D, magnitude: D,
+    comp_delta: F)
+    where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float, F: FnOnce((V, V)) -> V
+{
+    //Create delta vector between the two positions.
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let softened = (delta.displacement_squared() + softening.powi(2)).sqrt().powi(3);
+    if softened.is_normal() {
+        let force = delta * magnitude / softened * lhs.quanta() * rhs.quanta();
+        lhs.impulse(&force);
+        rhs.impulse(&-force);
+    }
+}
+
+///Same as gravitate_softened, but the softening length is the quadrature sum of the softening of both particles.
+pub fn gravitate_self_softened<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
+    where T1: PhysicsParticle<V, D> + Softening<D>, T2: PhysicsParticle<V, D> + Softening<D>, V: Vector<D>, D: Float
+{
+    let softening = lhs.softening().hypot(rhs.softening());
+    gravitate_softened(lhs, rhs, softening, magnitude);
+}
+
+///Same as gravitate_self_softened, but uses a special comp_delta closure to compute the delta between the particles
+pub fn gravitate_self_softened_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D,
+    comp_delta: F)
+    where T1: PhysicsParticle<V, D> + Softening<D>, T2: PhysicsParticle<V, D> + Softening<D>, V: Vector<D>, D: Float,
+    F: FnOnce((V, V)) -> V
+{
+    let softening = lhs.softening().hypot(rhs.softening());
+    gravitate_softened_delta(lhs, rhs, softening, magnitude, comp_delta);
+}
+
+#[test]
+fn gravitate_self_softened_test() {
+    use self::num::Zero;
+    type P = SoftenedParticle<Cartesian2<f64>, f64>;
+    let pull = |softening: f64| {
+        let mut a = P::new(1.0, softening, Cartesian2::new(0.0, 0.0), Cartesian2::zero(), 1.0);
+        let b = P::new(1.0, 0.0, Cartesian2::new(0.1, 0.0), Cartesian2::zero(), 1.0);
+        gravitate_self_softened(&a, &b, 1.0);
+        a.advance(1.0);
+        a.velocity().x
+    };
+    assert!((pull(0.0) - 100.0).abs() < 1e-9);
+    assert!(pull(1.0) > 0.0 && pull(1.0) < 0.01 * pull(0.0));
+    //Both lengths combine, so softening either particle has the same effect.
+    let mut a = P::new(1.0, 0.3, Cartesian2::new(0.0, 0.0), Cartesian2::zero(), 1.0);
+    let b = P::new(1.0, 0.4, Cartesian2::new(0.1, 0.0), Cartesian2::zero(), 1.0);
+    gravitate_self_softened(&a, &b, 1.0);
+    a.advance(1.0);
+    let mut c = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::zero(), 1.0);
+    gravitate_softened(&c, &BasicParticle::new(1.0, Cartesian2::new(0.1, 0.0), Cartesian2::zero(), 1.0), 0.5, 1.0);
+    c.advance(1.0);
+    assert!((a.velocity().x - c.velocity.x).abs() < 1e-12);
+}
+
+#[test]
+fn gravitate_softened_test() {
+    use self::num::Zero;
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian3::new(1.0, 2.0, 3.0), Cartesian3::zero(), 1.0);
+    let mut b = a.clone();
+    gravitate_softened(&a, &b, 0.1, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert!(a.velocity.x.is_finite() && a.velocity.y.is_finite() && a.velocity.z.is_finite());
+    assert!(b.velocity.x.is_finite() && b.velocity.y.is_finite() && b.velocity.z.is_finite());
+
+    //Far away the softening barely changes the force.
+    let mut a = P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    let mut b = P::new(1.0, Cartesian3::new(100.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    gravitate_softened(&a, &b, 0.1, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert!((a.velocity.x - 1e-4).abs() < 1e-9);
+}
+
+#[test]
+fn gravitate_to_mut_center_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut sun = P::new(1000.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1000.0);
+    let mut satellite = P::new(1.0, Cartesian2::new(10.0, 0.0), Cartesian2::new(0.0, 10.0), 1.0);
+    let momentum = sun.momentum() + satellite.momentum();
+    let (sun_start, satellite_start) = (sun.position, satellite.position);
+    for _ in 0..100 {
+        satellite.gravitate_to_mut_center(&sun, 1.0);
+        sun.advance(0.01);
+        satellite.advance(0.01);
+    }
+    let (sun_moved, satellite_moved) = ((sun.position - sun_start).displacement(),
+        (satellite.position - satellite_start).displacement());
+    assert!(sun_moved > 0.0 && sun_moved * 500.0 < satellite_moved);
+    assert!((sun.momentum() + satellite.momentum() - momentum).displacement() < 1e-9);
+}
+
 #[test]
 fn gravitate_test() {
     type P = BasicParticle<Cartesian3<f64>, f64>;
@@ -286,6 +1090,108 @@ pub fn gravitate_radius_squared_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1,
     }
 }
 
+///Apply inverse square repulsion between two physics particles, which is gravitate with the direction flipped.
+pub fn repel<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
+    where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+    let delta = rhs.position() - lhs.position();
+    let distance = delta.displacement();
+    if distance.is_normal() {
+        let force = delta * magnitude / distance.powi(3) * lhs.quanta() * rhs.quanta();
+        //Push lhs away from rhs, unlike gravitate.
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+///Same as repel, but uses a special comp_delta closure to compute the delta between the particles
+pub fn repel_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D, comp_delta: F)
+    where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float, F: FnOnce((V, V)) -> V
+{
+    //Create delta vector between the two positions.
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let distance = delta.displacement();
+    if distance.is_normal() {
+        let force = delta * magnitude / distance.powi(3) * lhs.quanta() * rhs.quanta();
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+///Same as gravitate_radius with the direction flipped, so the repulsion stops growing once the balls overlap.
+pub fn repel_radius<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
+    where T1: PhysicsParticle<V, D> + Ball<D>, T2: PhysicsParticle<V, D> + Ball<D>, V: Vector<D>, D: Float
+{
+    let delta = rhs.position() - lhs.position();
+    let distance_squared = delta.displacement_squared();
+    if distance_squared.is_normal() {
+        let radius_squared = (lhs.radius() + rhs.radius()).powi(2);
+        let force = delta * magnitude * lhs.quanta() * rhs.quanta() / if distance_squared > radius_squared {
+            distance_squared.sqrt().powi(3)
+        } else {
+            radius_squared
+        };
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+///Same as repel_radius, but uses a special comp_delta closure to compute the delta between the particles
+pub fn repel_radius_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D, comp_delta: F)
+    where T1: PhysicsParticle<V, D> + Ball<D>, T2: PhysicsParticle<V, D> + Ball<D>, V: Vector<D>, D: Float,
+    F: FnOnce((V, V)) -> V
+{
+    //Create delta vector between the two positions.
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let distance_squared = delta.displacement_squared();
+    if distance_squared.is_normal() {
+        let radius_squared = (lhs.radius() + rhs.radius()).powi(2);
+        let force = delta * magnitude * lhs.quanta() * rhs.quanta() / if distance_squared > radius_squared {
+            distance_squared.sqrt().powi(3)
+        } else {
+            radius_squared
+        };
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+#[test]
+fn repel_test() {
+    use self::num::Zero;
+    type P = BallParticle<Cartesian2<f64>, f64>;
+    let mut particles: Vec<P> = (0..12).map(|i| {
+        let angle = i as f64 * 2.4;
+        let position = Cartesian2::new(angle.cos(), angle.sin()) * (0.05 * i as f64);
+        P::new(1.0, position, Cartesian2::zero(), 1.0, 0.1)
+    }).collect();
+    let bounding = |particles: &[P]| {
+        let center = particles.iter().fold(Cartesian2::zero(), |acc, p| acc + p.position()) / particles.len() as f64;
+        particles.iter().fold(0.0f64, |radius, p| radius.max((p.position() - center).displacement()))
+    };
+    let mut radius = bounding(&particles);
+    for _ in 0..20 {
+        for i in 0..particles.len() {
+            for j in i + 1..particles.len() {
+                repel_radius(&particles[i], &particles[j], 0.01);
+            }
+        }
+        for p in &mut particles {
+            p.advance(0.1);
+        }
+        let next = bounding(&particles);
+        assert!(next > radius);
+        radius = next;
+    }
+
+    let mut a = BasicParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::zero(), 1.0);
+    let mut b = BasicParticle::new(1.0, Cartesian2::new(2.0, 0.0), Cartesian2::zero(), 1.0);
+    repel(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert_eq!((a.velocity.x, b.velocity.x), (-0.25, 0.25));
+}
+
 ///Apply spring forces between two particles.
 pub fn hooke<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
     where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float
@@ -353,6 +1259,61 @@ pub fn hooke_equilibrium_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &
     }
 }
 
+///Apply a spring with specified equilibrium distance in parallel with a damper between two particles.
+///
+///The spring is the same as hooke_equilibrium. The damper pushes against the relative velocity along the separation
+///axis with the force -damping * (v_rel . r) r on rhs, where r is the unit vector from lhs to rhs. The damping is
+///capped at the reduced mass over dt, the most that can be applied over a step of dt without reversing the relative
+///velocity, so a stiff damper can't make the explicit integration blow up.
+pub fn hooke_damped<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, equilibrium: D, magnitude: D, damping: D,
+    dt: D)
+    where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+    hooke_damped_delta(lhs, rhs, equilibrium, magnitude, damping, dt, |(l, r)| r - l);
+}
+
+///Same as hooke_damped, but uses a comp_delta closure to compute the distance from the first to second parameter
+pub fn hooke_damped_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, equilibrium: D, magnitude: D,
+    damping: D, dt: D, comp_delta: F)
+    where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D>, D: Float, F: FnOnce((V, V)) -> V
+{
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let displace = delta.displacement();
+    if displace.is_normal() {
+        let unit = delta / displace;
+        let reduced = lhs.inertia() * rhs.inertia() / (lhs.inertia() + rhs.inertia());
+        let damping = damping.min(reduced / dt);
+        let closing = V::dot(&(rhs.velocity() - lhs.velocity()), &unit);
+        let force = unit * (magnitude * (displace - equilibrium) * lhs.quanta() * rhs.quanta() + damping * closing);
+        lhs.impulse(&force);
+        rhs.impulse(&-force);
+    }
+}
+
+#[test]
+fn hooke_damped_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    let mut b = P::new(1.0, Cartesian2::new(2.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    let dt = 0.01;
+    //Record the largest stretch from equilibrium in each of the first and last second.
+    let (mut first, mut last) = (0.0f64, 0.0f64);
+    for i in 0..2000 {
+        hooke_damped(&a, &b, 1.0, 1.0, 0.2, dt);
+        a.advance(dt);
+        b.advance(dt);
+        let stretch = ((b.position - a.position).displacement() - 1.0).abs();
+        if i < 100 {
+            first = first.max(stretch);
+        } else if i >= 1900 {
+            last = last.max(stretch);
+        }
+    }
+    assert!(first > 0.9);
+    assert!(last < 0.1 * first);
+    assert!((a.momentum() + b.momentum()).displacement() < 1e-12);
+}
+
 ///Apply lorentz forces between two PhysicsParticle objects based on quanta, position, and velocity.
 pub fn lorentz<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
     where T1: PhysicsParticle<V, D>, T2: PhysicsParticle<V, D>, V: Vector<D> + CrossVector, D: Float
@@ -481,3 +1442,72 @@ pub fn lorentz_radius_squared_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, r
         rhs.impulse(&force);
     }
 }
+
+///Apply the magnetic dipole-dipole force between two particles, which depends on both moments and the direction
+///between the particles.
+///
+///Dipoles aligned head to tail along the line between them attract, while parallel dipoles side by side repel.
+pub fn dipole_dipole<V, D, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D)
+    where T1: Particle<V, D> + MagneticMoment<V>, T2: Particle<V, D> + MagneticMoment<V>, V: Vector<D>, D: Float
+{
+    let delta = rhs.position() - lhs.position();
+    let distance = delta.displacement();
+    if distance.is_normal() {
+        let (dir, lm, rm) = (delta / distance, lhs.moment(), rhs.moment());
+        let (ld, rd) = (V::dot(&lm, &dir), V::dot(&rm, &dir));
+        let three = D::one() + D::one() + D::one();
+        //This is the force on rhs, which points away from lhs when the dipoles repel.
+        let force = (rm * ld + lm * rd + dir * V::dot(&lm, &rm) - dir * (ld * rd * (three + D::one() + D::one()))) *
+            three * magnitude / distance.powi(4);
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+///Same as dipole_dipole, but uses a comp_delta closure to compute the delta from the first to second parameter
+pub fn dipole_dipole_delta<V, D, F, T1: ?Sized, T2: ?Sized>(lhs: &T1, rhs: &T2, magnitude: D, comp_delta: F)
+    where T1: Particle<V, D> + MagneticMoment<V>, T2: Particle<V, D> + MagneticMoment<V>, V: Vector<D>, D: Float,
+    F: FnOnce((V, V)) -> V
+{
+    //Create delta vector between the two positions.
+    let delta = comp_delta((lhs.position(), rhs.position()));
+    let distance = delta.displacement();
+    if distance.is_normal() {
+        let (dir, lm, rm) = (delta / distance, lhs.moment(), rhs.moment());
+        let (ld, rd) = (V::dot(&lm, &dir), V::dot(&rm, &dir));
+        let three = D::one() + D::one() + D::one();
+        let force = (rm * ld + lm * rd + dir * V::dot(&lm, &rm) - dir * (ld * rd * (three + D::one() + D::one()))) *
+            three * magnitude / distance.powi(4);
+        lhs.impulse(&-force);
+        rhs.impulse(&force);
+    }
+}
+
+#[test]
+fn dipole_dipole_test() {
+    use self::num::Zero;
+    type P = MagneticParticle<Cartesian3<f64>, f64>;
+    let up = Cartesian3::new(0.0, 0.0, 1.0);
+    //Head to tail along z attracts with 6 / r^4.
+    let mut a = P::new(1.0, up, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    let mut b = P::new(1.0, up, Cartesian3::new(0.0, 0.0, 1.0), Cartesian3::zero(), 1.0);
+    dipole_dipole(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert!((a.velocity() - up * 6.0).displacement() < 1e-12);
+    assert!((b.velocity() + up * 6.0).displacement() < 1e-12);
+    //Side by side along x repels with 3 / r^4.
+    let mut a = P::new(1.0, up, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    let mut b = P::new(1.0, up, Cartesian3::new(2.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    dipole_dipole(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert!((b.velocity() - Cartesian3::new(3.0 / 16.0, 0.0, 0.0)).displacement() < 1e-12);
+    assert!((a.velocity() + b.velocity()).displacement() < 1e-12);
+    //Flipping one moment flips both forces.
+    let a = P::new(1.0, up, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    let mut c = P::new(1.0, -up, Cartesian3::new(2.0, 0.0, 0.0), Cartesian3::zero(), 1.0);
+    dipole_dipole(&a, &c, 1.0);
+    c.advance(1.0);
+    assert!((c.velocity() + Cartesian3::new(3.0 / 16.0, 0.0, 0.0)).displacement() < 1e-12);
+}
@@ -0,0 +1,126 @@
+extern crate num;
+use self::num::Float;
+use super::BasicParticle;
+use super::super::vector::*;
+use rand::Rng;
+use std::ops::Range;
+
+///Sample a value uniformly from range.
+fn sample<D, R: ?Sized>(range: &Range<D>, rng: &mut R) -> D
+    where D: Float, R: Rng
+{
+    range.start + (range.end - range.start) * num::cast(rng.gen::<f64>()).unwrap()
+}
+
+///Sample a value from the standard normal distribution with the Box-Muller transform.
+pub(crate) fn standard_normal<D, R: ?Sized>(rng: &mut R) -> D
+    where D: Float, R: Rng
+{
+    //Shift the first sample into (0, 1] so that its logarithm is finite.
+    let u: D = num::cast(1.0 - rng.gen::<f64>()).unwrap();
+    let v: D = num::cast(rng.gen::<f64>()).unwrap();
+    let two = D::one() + D::one();
+    (-two * u.ln()).sqrt() * (two * num::cast::<_, D>(::std::f64::consts::PI).unwrap() * v).cos()
+}
+
+///Generate n particles with positions sampled uniformly inside bounds.
+///
+///Each particle gets a mass sampled from mass_range, which is used as both its quanta and its inertia, and every
+///component of its velocity is sampled separately from vel_range.
+pub fn random_particles<V, D, R: ?Sized>(n: usize, bounds: &Box<V>, mass_range: Range<D>, vel_range: Range<D>,
+    rng: &mut R) -> Vec<BasicParticle<V, D>>
+    where V: Vector<D>, D: Float, R: Rng
+{
+    let unit = -D::one()..D::one();
+    let mut components = vec![D::zero(); V::dimensions()];
+    (0..n).map(|_| {
+        for (i, c) in components.iter_mut().enumerate() {
+            *c = bounds.origin.get(i) + bounds.offset.get(i) * sample(&unit, rng);
+        }
+        let position = V::from_slice(&components);
+        for c in components.iter_mut() {
+            *c = sample(&vel_range, rng);
+        }
+        let velocity = V::from_slice(&components);
+        let mass = sample(&mass_range, rng);
+        BasicParticle::new(mass, position, velocity, mass)
+    }).collect()
+}
+
+///Sample a direction uniformly from the unit sphere.
+fn unit_sphere<D, R: ?Sized>(rng: &mut R) -> Cartesian3<D>
+    where D: Float, R: Rng
+{
+    let z = sample(&(-D::one()..D::one()), rng);
+    let phi = sample(&(D::zero()..num::cast(2.0 * ::std::f64::consts::PI).unwrap()), rng);
+    let ring = (D::one() - z * z).sqrt();
+    Cartesian3{x: ring * phi.cos(), y: ring * phi.sin(), z: z}
+}
+
+///Generate n particles of equal mass sampled from a Plummer sphere with a gravitational constant of 1.
+///
+///This follows Aarseth, Henon and Wielen (1974). Radii come from inverting the cumulative mass profile, and speeds
+///are a fraction q of the local escape velocity, where q is sampled by rejection from q^2 (1 - q^2)^(7/2). The result
+///is in equilibrium, so it stays roughly the same size when stepped with gravitate at a magnitude of 1.
+pub fn plummer_sphere<D, R: ?Sized>(n: usize, total_mass: D, scale_radius: D, rng: &mut R)
+    -> Vec<BasicParticle<Cartesian3<D>, D>>
+    where D: Float, R: Rng
+{
+    let mass = total_mass / num::cast(n).unwrap();
+    let unit = D::zero()..D::one();
+    (0..n).map(|_| {
+        //A mass fraction of zero would put the particle at the center, but one would put it at infinity.
+        let mut fraction = D::zero();
+        while fraction.is_zero() {
+            fraction = sample(&unit, rng);
+        }
+        let r = scale_radius / (fraction.powf(num::cast(-2.0 / 3.0).unwrap()) - D::one()).sqrt();
+        let escape = (num::cast::<_, D>(2.0).unwrap() * total_mass / scale_radius).sqrt() *
+            (D::one() + (r / scale_radius).powi(2)).powf(num::cast(-0.25).unwrap());
+        //The density of q peaks below 0.1, so sampling the height from [0, 0.1) covers it.
+        let q = loop {
+            let q = sample(&unit, rng);
+            let height = sample(&(D::zero()..num::cast(0.1).unwrap()), rng);
+            if height < q * q * (D::one() - q * q).powf(num::cast(3.5).unwrap()) {
+                break q;
+            }
+        };
+        BasicParticle::new(mass, unit_sphere(rng) * r, unit_sphere(rng) * (q * escape), mass)
+    }).collect()
+}
+
+#[test]
+fn plummer_sphere_test() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use super::PhysicsParticle;
+    let mut rng = StdRng::seed_from_u64(1974);
+    let particles = plummer_sphere(5000, 2.0, 1.5, &mut rng);
+    assert!(particles.iter().all(|p| p.quanta == 2.0 / 5000.0));
+    let mut radii: Vec<f64> = particles.iter().map(|p| p.position.displacement()).collect();
+    radii.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    //Half of the mass of a Plummer sphere lies within a / sqrt(2^(2/3) - 1).
+    let half_mass = 1.5 / (2.0f64.powf(2.0 / 3.0) - 1.0).sqrt();
+    assert!((radii[2500] - half_mass).abs() < 0.05 * half_mass);
+    //A cluster in equilibrium satisfies the virial theorem 2T = -W, and W = -3 pi G M^2 / (32 a).
+    let kinetic: f64 = particles.iter().map(|p| p.kinetic_energy()).sum();
+    let potential = -3.0 * ::std::f64::consts::PI * 4.0 / (32.0 * 1.5);
+    assert!((2.0 * kinetic / -potential - 1.0).abs() < 0.1);
+}
+
+#[test]
+fn random_particles_test() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    let mut rng = StdRng::seed_from_u64(42);
+    let bounds = Box::from_corners(Cartesian3::new(-1.0, 2.0, 0.0), Cartesian3::new(3.0, 2.5, 10.0));
+    let particles = random_particles(500, &bounds, 0.5..2.0, -0.1..0.1, &mut rng);
+    assert_eq!(particles.len(), 500);
+    for p in &particles {
+        let delta = p.position - bounds.origin;
+        assert!(delta.x.abs() <= bounds.offset.x && delta.y.abs() <= bounds.offset.y &&
+            delta.z.abs() <= bounds.offset.z);
+        assert!(p.quanta >= 0.5 && p.quanta < 2.0 && p.quanta == p.inertia);
+        assert!(p.velocity.x.abs() <= 0.1 && p.velocity.y.abs() <= 0.1 && p.velocity.z.abs() <= 0.1);
+    }
+}
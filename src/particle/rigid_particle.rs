@@ -0,0 +1,146 @@
+extern crate num;
+use self::num::{Float, Zero, FromPrimitive};
+use super::*;
+use super::super::Vector;
+use super::super::matrix::Matrix3;
+use std::ops::{Add, Mul, Div};
+
+///A vector whose space has a rotational state, which is an angle in 2d and a rotation matrix in 3d.
+pub trait Rotation<D> {
+    ///The type of torques and angular velocities, which is a scalar in 2d and a vector along the axis in 3d.
+    type Angular: Copy + Zero + Add<Output=Self::Angular> + Mul<D, Output=Self::Angular> + Div<D, Output=Self::Angular>;
+    ///The type of orientations.
+    type Orientation: Copy;
+    ///The orientation of an object that has not rotated.
+    fn identity_orientation() -> Self::Orientation;
+    ///Rotate an orientation at a constant angular velocity for time.
+    fn rotate(orientation: &Self::Orientation, angular_velocity: &Self::Angular, time: D) -> Self::Orientation;
+}
+
+impl<D> Rotation<D> for Cartesian2<D>
+    where D: Float
+{
+    type Angular = D;
+    type Orientation = D;
+    fn identity_orientation() -> D {
+        D::zero()
+    }
+    fn rotate(orientation: &D, angular_velocity: &D, time: D) -> D {
+        *orientation + *angular_velocity * time
+    }
+}
+
+impl<D> Rotation<D> for Cartesian3<D>
+    where D: Float + FromPrimitive
+{
+    type Angular = Self;
+    type Orientation = Matrix3<D>;
+    fn identity_orientation() -> Matrix3<D> {
+        Matrix3::identity()
+    }
+    fn rotate(orientation: &Matrix3<D>, angular_velocity: &Self, time: D) -> Matrix3<D> {
+        let speed = angular_velocity.displacement();
+        if speed.is_normal() {
+            Matrix3::from_axis_angle(*angular_velocity, speed * time) * *orientation
+        } else {
+            *orientation
+        }
+    }
+}
+
+///An object that has an orientation and spins with an angular velocity
+pub trait Oriented<V, D>
+    where V: Rotation<D>
+{
+    //Get the orientation of the object.
+    fn orientation(&self) -> V::Orientation;
+    //Get the angular velocity of the object.
+    fn angular_velocity(&self) -> V::Angular;
+}
+
+///RigidParticle is a BasicParticle that also rotates with a scalar moment of inertia.
+///
+///The moment of inertia is the same around every axis, like a uniform ball. Torques accumulate like forces and are
+///consumed by advance_rotation, which is separate from advance so the rotation can be stepped on its own.
+pub struct RigidParticle<V, D>
+    where V: Rotation<D>
+{
+    pub particle: BasicParticle<V, D>,
+    pub moment_of_inertia: D,
+    pub orientation: V::Orientation,
+    pub angular_velocity: V::Angular,
+    torque: V::Angular,
+}
+
+impl<V, D> RigidParticle<V, D>
+    where V: Vector<D> + Rotation<D>, D: Float
+{
+    ///Create a particle that is not rotating and has the identity orientation.
+    pub fn new(quanta: D, position: V, velocity: V, inertia: D, moment_of_inertia: D) -> Self {
+        RigidParticle{
+            particle: BasicParticle::new(quanta, position, velocity, inertia),
+            moment_of_inertia: moment_of_inertia,
+            orientation: V::identity_orientation(),
+            angular_velocity: V::Angular::zero(),
+            torque: V::Angular::zero(),
+        }
+    }
+
+    ///Apply a torque that is consumed by the next advance_rotation.
+    pub fn apply_torque(&mut self, tau: &V::Angular) {
+        self.torque = self.torque + *tau;
+    }
+
+    ///Step the angular velocity by the accumulated torque and then rotate with it, like advance does for position.
+    pub fn advance_rotation(&mut self, time: D) {
+        self.angular_velocity = self.angular_velocity + self.torque / self.moment_of_inertia * time;
+        self.orientation = V::rotate(&self.orientation, &self.angular_velocity, time);
+        self.torque = V::Angular::zero();
+    }
+}
+
+impl<V, D> Oriented<V, D> for RigidParticle<V, D>
+    where V: Vector<D> + Rotation<D>, D: Float
+{
+    fn orientation(&self) -> V::Orientation {
+        self.orientation
+    }
+
+    fn angular_velocity(&self) -> V::Angular {
+        self.angular_velocity
+    }
+}
+
+delegate_basic_particle!(RigidParticle where V: Vector<D> + Rotation<D>, D: Float);
+
+#[test]
+fn constant_torque_test() {
+    let mut p = RigidParticle::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0, 2.0);
+    let dt = 0.01;
+    for n in 1..101 {
+        p.apply_torque(&4.0);
+        p.advance_rotation(dt);
+        //The angular acceleration is 2, so the angular velocity is 2t and the angle is t^2 up to the half step
+        //offset of semi-implicit Euler.
+        let t = n as f64 * dt;
+        assert!((p.angular_velocity() - 2.0 * t).abs() < 1e-12);
+        assert!((p.orientation() - t * (t + dt)).abs() < 1e-12);
+    }
+    //Without torque it keeps spinning at the same rate.
+    p.advance_rotation(dt);
+    assert!((p.angular_velocity() - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn rotate_3d_test() {
+    use std::f64::consts::FRAC_PI_2;
+    let mut p = RigidParticle::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(0.0, 0.0, 0.0), 1.0, 1.0);
+    //A torque of pi / 2 for one unit of time spins the particle a quarter turn around z every unit of time.
+    p.apply_torque(&Cartesian3::new(0.0, 0.0, FRAC_PI_2));
+    p.advance_rotation(1.0);
+    let x = p.orientation() * Cartesian3::new(1.0, 0.0, 0.0);
+    assert!((x - Cartesian3::new(0.0, 1.0, 0.0)).displacement() < 1e-12);
+    p.advance_rotation(1.0);
+    let x = p.orientation() * Cartesian3::new(1.0, 0.0, 0.0);
+    assert!((x - Cartesian3::new(-1.0, 0.0, 0.0)).displacement() < 1e-12);
+}
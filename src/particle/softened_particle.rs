@@ -0,0 +1,36 @@
+extern crate num;
+use self::num::Float;
+use super::*;
+use super::super::Vector;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///SoftenedParticle is a BasicParticle with its own gravitational softening length.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "V: Deserialize<'de> + num::Zero, D: Deserialize<'de>")))]
+pub struct SoftenedParticle<V, D> {
+    pub particle: BasicParticle<V, D>,
+    pub softening: D,
+}
+
+impl<V, D> SoftenedParticle<V, D> {
+    pub fn new(quanta: D, softening: D, position: V, velocity: V, inertia: D) -> Self
+        where V: num::Zero
+    {
+        SoftenedParticle{
+            particle: BasicParticle::new(quanta, position, velocity, inertia),
+            softening: softening,
+        }
+    }
+}
+
+impl<V, D> Softening<D> for SoftenedParticle<V, D>
+    where D: Float
+{
+    fn softening(&self) -> D {
+        self.softening
+    }
+}
+
+delegate_basic_particle!(SoftenedParticle where V: Vector<D>, D: Float);
@@ -0,0 +1,101 @@
+//!Contains ready made initial conditions for well known gravitational systems, which are useful for teaching and
+//!testing integrators
+
+extern crate num;
+use self::num::Float;
+use super::vector::Cartesian2;
+use super::particle::BasicParticle;
+use super::system::ParticleSystem;
+
+///The period of the figure eight three body orbit with a gravitational constant and masses of one.
+pub const FIGURE_EIGHT_PERIOD: f64 = 6.32591398;
+
+fn cast<D>(n: f64) -> D
+    where D: Float
+{
+    num::cast(n).unwrap()
+}
+
+fn body<D>(quanta: f64, position: (f64, f64), velocity: (f64, f64)) -> BasicParticle<Cartesian2<D>, D>
+    where D: Float
+{
+    BasicParticle::new(cast(quanta), Cartesian2::new(cast(position.0), cast(position.1)),
+        Cartesian2::new(cast(velocity.0), cast(velocity.1)), cast(quanta))
+}
+
+///Create the figure eight choreography found by Chenciner and Montgomery, where three equal masses chase each other
+///around the same figure eight shaped path.
+///
+///The system should be stepped with a gravitational constant of one. The bodies return to their initial
+///conditions every FIGURE_EIGHT_PERIOD, and the total momentum is zero.
+pub fn figure_eight_three_body<D>() -> ParticleSystem<BasicParticle<Cartesian2<D>, D>, Cartesian2<D>, D>
+    where D: Float
+{
+    let (x, y) = (0.97000436, -0.24308753);
+    let (vx, vy) = (-0.93240737, -0.86473146);
+    let mut system = ParticleSystem::with_capacity(3);
+    system.push(body(1.0, (x, y), (-vx / 2.0, -vy / 2.0)));
+    system.push(body(1.0, (-x, -y), (-vx / 2.0, -vy / 2.0)));
+    system.push(body(1.0, (0.0, 0.0), (vx, vy)));
+    system
+}
+
+///The gravitational constant in astronomical units, solar masses, and years, which is 4 pi^2.
+pub fn solar_gravitational_constant<D>() -> D
+    where D: Float
+{
+    cast(4.0 * ::std::f64::consts::PI * ::std::f64::consts::PI)
+}
+
+///Create the sun, earth, and moon in that order on circular orbits in the plane of the ecliptic.
+///
+///Positions are in astronomical units, velocities in astronomical units per year, and quanta in solar masses, so the
+///system should be stepped with solar_gravitational_constant and time in years. The sun moves so that the total
+///momentum is zero.
+pub fn sun_earth_moon<D>() -> ParticleSystem<BasicParticle<Cartesian2<D>, D>, Cartesian2<D>, D>
+    where D: Float
+{
+    let tau = 2.0 * ::std::f64::consts::PI;
+    let (earth, moon) = (3.003e-6, 3.694e-8);
+    let lunar_distance = 0.00257;
+    let lunar_speed = tau * ((earth + moon) / lunar_distance).sqrt();
+    //The earth and moon orbit their barycenter, which orbits the sun at one astronomical unit.
+    let (earth_share, moon_share) = (moon / (earth + moon), earth / (earth + moon));
+    let mut system = ParticleSystem::with_capacity(3);
+    system.push(body(1.0, (0.0, 0.0), (0.0, -(earth + moon) * tau)));
+    system.push(body(earth, (1.0 - earth_share * lunar_distance, 0.0), (0.0, tau - earth_share * lunar_speed)));
+    system.push(body(moon, (1.0 + moon_share * lunar_distance, 0.0), (0.0, tau + moon_share * lunar_speed)));
+    system
+}
+
+#[test]
+fn figure_eight_period_test() {
+    use super::vector::Vector;
+    let mut system = figure_eight_three_body::<f64>();
+    let start: Vec<_> = system.iter().map(|p| (p.position, p.velocity)).collect();
+    let steps = 10000;
+    for _ in 0..steps {
+        system.step_gravity_leapfrog(1.0, FIGURE_EIGHT_PERIOD / steps as f64);
+    }
+    for (p, &(position, velocity)) in system.iter().zip(start.iter()) {
+        assert!((p.position - position).displacement() < 1e-5);
+        assert!((p.velocity - velocity).displacement() < 1e-5);
+    }
+}
+
+#[test]
+fn sun_earth_moon_test() {
+    use super::vector::Vector;
+    use super::particle::total_momentum;
+    let mut system = sun_earth_moon::<f64>();
+    assert!(total_momentum(system.iter()).displacement() < 1e-15);
+    let g = solar_gravitational_constant();
+    let steps = 10000;
+    for _ in 0..steps {
+        system.step_gravity_leapfrog(g, 1.0 / steps as f64);
+    }
+    //After a year the earth is back where it started and the moon is still bound to it.
+    let p: Vec<_> = system.iter().collect();
+    assert!((p[1].position - Cartesian2::new(1.0, 0.0)).displacement() < 1e-4);
+    assert!(((p[2].position - p[1].position).displacement() - 0.00257).abs() < 2e-4);
+}
@@ -0,0 +1,252 @@
+extern crate num;
+use self::num::Float;
+use super::super::vector::*;
+use super::super::particle::*;
+use std::mem;
+
+///Trees stop subdividing at this depth so that coincident particles can share a leaf.
+const MAX_DEPTH: usize = 32;
+
+///A vector whose space can be split into orthants around a point, which is a quadrant in 2d and an octant in 3d.
+pub trait Orthant<D>: Vector<D>
+    where D: Float
+{
+    ///The amount of orthants around a point
+    fn orthants() -> usize;
+
+    ///Index of the orthant this position lies in around center
+    fn orthant(&self, center: &Self) -> usize;
+
+    ///Center of orthant i of the cube around this point with a half width of half
+    fn orthant_center(&self, i: usize, half: D) -> Self;
+
+    ///Check if this position lies inside the cube around center with a half width of half
+    fn within(&self, center: &Self, half: D) -> bool;
+}
+
+impl<D> Orthant<D> for Cartesian2<D>
+    where D: Float + num::FromPrimitive
+{
+    fn orthants() -> usize {
+        4
+    }
+
+    fn orthant(&self, center: &Self) -> usize {
+        (self.x >= center.x) as usize | ((self.y >= center.y) as usize) << 1
+    }
+
+    fn orthant_center(&self, i: usize, half: D) -> Self {
+        let quarter = half / D::from_u32(2u32).unwrap();
+        Cartesian2{
+            x: self.x + if i & 1 != 0 {quarter} else {-quarter},
+            y: self.y + if i & 2 != 0 {quarter} else {-quarter},
+        }
+    }
+
+    fn within(&self, center: &Self, half: D) -> bool {
+        (self.x - center.x).abs() <= half && (self.y - center.y).abs() <= half
+    }
+}
+
+impl<D> Orthant<D> for Cartesian3<D>
+    where D: Float + num::FromPrimitive
+{
+    fn orthants() -> usize {
+        8
+    }
+
+    fn orthant(&self, center: &Self) -> usize {
+        (self.x >= center.x) as usize | ((self.y >= center.y) as usize) << 1 | ((self.z >= center.z) as usize) << 2
+    }
+
+    fn orthant_center(&self, i: usize, half: D) -> Self {
+        let quarter = half / D::from_u32(2u32).unwrap();
+        Cartesian3{
+            x: self.x + if i & 1 != 0 {quarter} else {-quarter},
+            y: self.y + if i & 2 != 0 {quarter} else {-quarter},
+            z: self.z + if i & 4 != 0 {quarter} else {-quarter},
+        }
+    }
+
+    fn within(&self, center: &Self, half: D) -> bool {
+        (self.x - center.x).abs() <= half && (self.y - center.y).abs() <= half && (self.z - center.z).abs() <= half
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Body<V, D> {
+    position: V,
+    quanta: D,
+    inertia: D,
+}
+
+struct Node<V, D> {
+    center: V,
+    half: D,
+    quanta: D,
+    //Sum of the positions weighted by quanta, which becomes the center of quanta when divided by quanta.
+    weighted: V,
+    children: Vec<usize>,
+    bodies: Vec<usize>,
+}
+
+///A Barnes-Hut tree which approximates the gravitation of far away groups of particles by their center of quanta.
+///
+///This is a quadtree for Cartesian2 and an octree for Cartesian3. A group is approximated when the width of its node
+///divided by the distance to its center of quanta is below theta, so a theta of zero computes every pair directly.
+pub struct BarnesHut<V, D> {
+    theta: D,
+    bodies: Vec<Body<V, D>>,
+    nodes: Vec<Node<V, D>>,
+}
+
+impl<V, D> BarnesHut<V, D>
+    where V: Orthant<D>, D: Float
+{
+    ///Build a tree from a snapshot of the particles.
+    pub fn new<P>(particles: &[P], theta: D) -> Self
+        where P: PhysicsParticle<V, D>
+    {
+        let bodies: Vec<_> = particles.iter().map(|p| Body{
+            position: p.position(),
+            quanta: p.quanta(),
+            inertia: p.inertia(),
+        }).collect();
+
+        //Every component is within the displacement, so a cube this wide around the mean holds every particle.
+        let center = if bodies.is_empty() {
+            V::zero()
+        } else {
+            bodies.iter().fold(V::zero(), |acc, b| acc + b.position) / num::cast(bodies.len()).unwrap()
+        };
+        let half = bodies.iter().fold(D::zero(), |acc, b| acc.max((b.position - center).displacement()));
+
+        let mut tree = BarnesHut{
+            theta: theta,
+            bodies: bodies,
+            nodes: vec![Node::new(center, if half.is_normal() {half} else {D::one()})],
+        };
+        for i in 0..tree.bodies.len() {
+            tree.insert(0, i, 0);
+        }
+        tree
+    }
+
+    fn insert(&mut self, node: usize, body: usize, depth: usize) {
+        let Body{position, quanta, ..} = self.bodies[body];
+        {
+            let node = &mut self.nodes[node];
+            node.quanta = node.quanta + quanta;
+            node.weighted = node.weighted + position * quanta;
+        }
+        if self.nodes[node].children.is_empty() {
+            if self.nodes[node].bodies.is_empty() || depth == MAX_DEPTH {
+                self.nodes[node].bodies.push(body);
+                return;
+            }
+            //Split the leaf and move its existing bodies down into the new children.
+            let (center, half) = (self.nodes[node].center, self.nodes[node].half);
+            let first = self.nodes.len();
+            for i in 0..V::orthants() {
+                self.nodes.push(Node::new(center.orthant_center(i, half), half / (D::one() + D::one())));
+            }
+            self.nodes[node].children = (first..first + V::orthants()).collect();
+            for existing in mem::take(&mut self.nodes[node].bodies) {
+                let child = self.nodes[node].children[self.bodies[existing].position.orthant(&center)];
+                self.insert(child, existing, depth + 1);
+            }
+        }
+        let child = self.nodes[node].children[position.orthant(&self.nodes[node].center)];
+        self.insert(child, body, depth + 1);
+    }
+
+    ///Compute the acceleration gravitate_to would give each particle from every other particle in the tree.
+    pub fn compute_accelerations(&self, magnitude: D) -> Vec<V> {
+        (0..self.bodies.len()).map(|i| self.acceleration(i, magnitude)).collect()
+    }
+
+    fn acceleration(&self, i: usize, magnitude: D) -> V {
+        let body = self.bodies[i];
+        let pull = |position: V, quanta: D| {
+            let delta = position - body.position;
+            let distance = delta.displacement();
+            if distance.is_normal() {
+                delta / distance.powi(3) * quanta
+            } else {
+                V::zero()
+            }
+        };
+
+        let mut accel = V::zero();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if node.quanta.is_zero() {
+                continue;
+            }
+            if node.children.is_empty() {
+                for &j in node.bodies.iter().filter(|&&j| j != i) {
+                    accel = accel + pull(self.bodies[j].position, self.bodies[j].quanta);
+                }
+            } else {
+                let centroid = node.weighted / node.quanta;
+                let distance = (centroid - body.position).displacement();
+                //A node holding this particle must be opened so that it doesn't attract itself.
+                if node.half + node.half < self.theta * distance && !body.position.within(&node.center, node.half) {
+                    accel = accel + pull(centroid, node.quanta);
+                } else {
+                    stack.extend(node.children.iter().cloned());
+                }
+            }
+        }
+        accel * magnitude * body.quanta / body.inertia
+    }
+}
+
+impl<V, D> Node<V, D>
+    where V: Vector<D>, D: Float
+{
+    fn new(center: V, half: D) -> Self {
+        Node{
+            center: center,
+            half: half,
+            quanta: D::zero(),
+            weighted: V::zero(),
+            children: Vec::new(),
+            bodies: Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn barnes_hut_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut random = super::test_random(12345);
+    let particles: Vec<P> = (0..200).map(|_| {
+        let position = Cartesian3::new(random(), random(), random()) * 10.0;
+        P::new(random() + 0.5, position, Cartesian3::new(0.0, 0.0, 0.0), random() + 0.5)
+    }).collect();
+
+    let approximate = BarnesHut::new(&particles, 0.3).compute_accelerations(1.0);
+    let exact = BarnesHut::new(&particles, 0.0).compute_accelerations(1.0);
+
+    //Check the direct tree evaluation against pairwise gravitate.
+    let mut direct = particles.clone();
+    for i in 0..direct.len() {
+        for j in i + 1..direct.len() {
+            gravitate(&direct[i], &direct[j], 1.0);
+        }
+    }
+    for (p, a) in direct.iter_mut().zip(exact.iter()) {
+        p.advance(1.0);
+        assert!((p.velocity - *a).displacement() <= 1e-9 * a.displacement());
+    }
+
+    let (mut error, mut total) = (0.0, 0.0);
+    for (a, e) in approximate.iter().zip(exact.iter()) {
+        assert!((*a - *e).displacement() < 0.05 * e.displacement());
+        error += (*a - *e).displacement_squared();
+        total += e.displacement_squared();
+    }
+    assert!((error / total).sqrt() < 0.01);
+}
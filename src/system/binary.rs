@@ -0,0 +1,129 @@
+extern crate num;
+use self::num::Float;
+use super::ParticleSystem;
+use super::super::vector::*;
+use super::super::particle::*;
+use super::super::error::StateError;
+use bincode;
+use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use std::any;
+use std::io::{Read, Write};
+
+//Identifies the start of a saved state and the version of its layout.
+const MAGIC: [u8; 4] = *b"ZOOM";
+const VERSION: u32 = 1;
+
+//Written before the particles so that a state is never decoded as the wrong vector or scalar type. Layouts with the
+//same dimension and scalar size, like Spherical and Cartesian3, are told apart by the names of the types.
+#[derive(Serialize, Deserialize, PartialEq)]
+struct Header {
+    magic: [u8; 4],
+    version: u32,
+    dimensions: u32,
+    vector: String,
+    scalar: String,
+}
+
+impl Header {
+    fn new<V, D>() -> Self
+        where V: Vector<D>, D: Float
+    {
+        Header{
+            magic: MAGIC,
+            version: VERSION,
+            dimensions: V::dimensions() as u32,
+            vector: any::type_name::<V>().to_string(),
+            scalar: any::type_name::<D>().to_string(),
+        }
+    }
+}
+
+///A particle system that can be saved to and loaded from a compact binary format
+///
+///The state starts with a header recording the vector dimension and the names of the vector and scalar types, which
+///load checks before it decodes any particles. The names come from std::any::type_name, which is not guaranteed to be
+///the same across compiler versions, so a state may be rejected by a build with another compiler. Only what serde
+///saves of each particle is kept, so forces that were applied but not advanced yet are lost.
+pub trait BinaryState: Sized {
+    //Write the state to writer.
+    fn save<W>(&self, writer: W) -> Result<(), StateError>
+        where W: Write;
+
+    //Read a state written by save, failing with StateError::Header if it was saved as a different type.
+    fn load<R>(reader: R) -> Result<Self, StateError>
+        where R: Read;
+}
+
+impl<V, D> BinaryState for ParticleSystem<BasicParticle<V, D>, V, D>
+    where V: Vector<D> + Serialize + DeserializeOwned, D: Float + Serialize + DeserializeOwned
+{
+    fn save<W>(&self, mut writer: W) -> Result<(), StateError>
+        where W: Write
+    {
+        bincode::serialize_into(&mut writer, &Header::new::<V, D>())?;
+        bincode::serialize_into(&mut writer, &self.particles)?;
+        Ok(())
+    }
+
+    fn load<R>(mut reader: R) -> Result<Self, StateError>
+        where R: Read
+    {
+        let header: Header = bincode::deserialize_from(&mut reader)?;
+        if header != Header::new::<V, D>() {
+            return Err(StateError::Header);
+        }
+        let mut system = ParticleSystem::new();
+        system.particles = bincode::deserialize_from(&mut reader)?;
+        Ok(system)
+    }
+}
+
+#[test]
+fn round_trip_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian3<f64>, f64> = ParticleSystem::new();
+    for i in 0..1000 {
+        let f = i as f64;
+        system.push(P::new(1.0 + f, Cartesian3::new(f, -f * 0.5, f.sin()), Cartesian3::new(f.cos(), 0.1, -f), 2.0));
+    }
+    let mut state = Vec::new();
+    system.save(&mut state).unwrap();
+    let loaded = ParticleSystem::<P, Cartesian3<f64>, f64>::load(&state[..]).unwrap();
+    assert_eq!(loaded.len(), 1000);
+    for (a, b) in system.iter().zip(loaded.iter()) {
+        assert_eq!((a.quanta, a.inertia), (b.quanta, b.inertia));
+        assert_eq!((a.position.x, a.position.y, a.position.z), (b.position.x, b.position.y, b.position.z));
+        assert_eq!((a.velocity.x, a.velocity.y, a.velocity.z), (b.velocity.x, b.velocity.y, b.velocity.z));
+    }
+}
+
+#[test]
+fn mismatched_header_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::default());
+    let mut state = Vec::new();
+    system.save(&mut state).unwrap();
+    match ParticleSystem::<BasicParticle<Cartesian3<f64>, f64>, Cartesian3<f64>, f64>::load(&state[..]) {
+        Err(StateError::Header) => {}
+        _ => panic!("a 2d state was loaded as 3d"),
+    }
+    match ParticleSystem::<BasicParticle<Cartesian2<f32>, f32>, Cartesian2<f32>, f32>::load(&state[..]) {
+        Err(StateError::Header) => {}
+        _ => panic!("an f64 state was loaded as f32"),
+    }
+    //Spherical has the same dimension and scalar as Cartesian3, but its components mean something else.
+    let mut spherical = Vec::new();
+    let mut system: ParticleSystem<BasicParticle<Spherical<f64>, f64>, Spherical<f64>, f64> = ParticleSystem::new();
+    system.push(BasicParticle::default());
+    system.save(&mut spherical).unwrap();
+    match ParticleSystem::<BasicParticle<Cartesian3<f64>, f64>, Cartesian3<f64>, f64>::load(&spherical[..]) {
+        Err(StateError::Header) => {}
+        _ => panic!("a spherical state was loaded as Cartesian3"),
+    }
+    match ParticleSystem::<P, Cartesian2<f64>, f64>::load(&b"not a state"[..]) {
+        Err(StateError::Header) | Err(StateError::Encoding(_)) => {}
+        _ => panic!("garbage was loaded as a state"),
+    }
+}
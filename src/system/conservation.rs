@@ -0,0 +1,76 @@
+extern crate num;
+use self::num::Float;
+use super::ParticleSystem;
+use super::super::vector::*;
+use super::super::particle::*;
+
+///Records the total energy of a gravitating ParticleSystem so the drift of an integrator can be measured later.
+pub struct ConservationMonitor<D> {
+    magnitude: D,
+    initial: D,
+}
+
+impl<D> ConservationMonitor<D>
+    where D: Float
+{
+    ///Record the kinetic plus gravitational potential energy of the system with the gravitational constant magnitude.
+    pub fn new<P, V>(system: &ParticleSystem<P, V, D>, magnitude: D) -> Self
+        where P: PhysicsParticle<V, D>, V: Vector<D>
+    {
+        ConservationMonitor{
+            magnitude: magnitude,
+            initial: system.gravitational_energy(magnitude),
+        }
+    }
+
+    ///The energy recorded at construction.
+    pub fn initial_energy(&self) -> D {
+        self.initial
+    }
+
+    ///Compute (E - E0) / |E0| for the current energy E of the system, which is zero if energy was conserved.
+    ///
+    ///If the initial energy E0 is zero, there is nothing to divide by, so the absolute drift E - E0 is returned.
+    pub fn relative_drift<P, V>(&self, system: &ParticleSystem<P, V, D>) -> D
+        where P: PhysicsParticle<V, D>, V: Vector<D>
+    {
+        let drift = system.gravitational_energy(self.magnitude) - self.initial;
+        if self.initial.is_zero() {drift} else {drift / self.initial.abs()}
+    }
+}
+
+#[test]
+fn drift_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let start = || {
+        let mut system = ParticleSystem::new();
+        system.push(P::new(1.0, Cartesian2::new(-0.5, 0.0), Cartesian2::new(0.0, -0.6), 1.0));
+        system.push(P::new(1.0, Cartesian2::new(0.5, 0.0), Cartesian2::new(0.0, 0.6), 1.0));
+        system.push(P::new(0.01, Cartesian2::new(3.0, 0.0), Cartesian2::new(0.0, 0.8), 0.01));
+        system
+    };
+    let mut euler = start();
+    let mut leapfrog = start();
+    let monitor = ConservationMonitor::new(&euler, 1.0);
+    assert_eq!(monitor.relative_drift(&leapfrog), 0.0);
+    for _ in 0..10000 {
+        euler.step_gravity(1.0, 0.001);
+        leapfrog.step_gravity_leapfrog(1.0, 0.001);
+    }
+    let (euler_drift, leapfrog_drift) = (monitor.relative_drift(&euler).abs(), monitor.relative_drift(&leapfrog).abs());
+    assert!(leapfrog_drift < 1e-5);
+    assert!(leapfrog_drift * 10.0 < euler_drift);
+}
+
+#[test]
+fn zero_energy_drift_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    //A single particle at rest has no kinetic or potential energy.
+    let mut system = ParticleSystem::new();
+    system.push(P::default());
+    let monitor = ConservationMonitor::new(&system, 1.0);
+    assert_eq!(monitor.initial_energy(), 0.0);
+    assert_eq!(monitor.relative_drift(&system), 0.0);
+    system.iter_mut().next().unwrap().velocity = Cartesian2::new(2.0, 0.0);
+    assert_eq!(monitor.relative_drift(&system), 2.0);
+}
@@ -0,0 +1,933 @@
+//!Contains containers that drive the interactions between collections of particles
+
+pub mod barnes_hut;
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod conservation;
+pub mod recorder;
+pub mod simulation;
+pub mod soa;
+pub mod spatial_hash;
+pub use self::barnes_hut::*;
+#[cfg(feature = "binary")]
+pub use self::binary::*;
+pub use self::conservation::*;
+pub use self::recorder::*;
+pub use self::simulation::*;
+pub use self::soa::*;
+pub use self::spatial_hash::*;
+
+extern crate num;
+use self::num::Float;
+use super::vector::*;
+use super::particle::*;
+use super::error::SimError;
+use std::marker::PhantomData;
+use std::slice;
+use std::ops::{Index, IndexMut};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+///A collection of particles that can be stepped forward in time together.
+pub struct ParticleSystem<P, V, D> {
+    particles: Vec<P>,
+    phantom: PhantomData<(V, D)>,
+}
+
+impl<P, V, D> ParticleSystem<P, V, D> {
+    pub fn new() -> Self {
+        ParticleSystem{
+            particles: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Create an empty system with room for capacity particles before it reallocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ParticleSystem{
+            particles: Vec::with_capacity(capacity),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Add a particle to the system.
+    pub fn push(&mut self, particle: P) {
+        self.particles.push(particle);
+    }
+
+    ///Reserve room for at least additional more particles.
+    pub fn reserve(&mut self, additional: usize) {
+        self.particles.reserve(additional);
+    }
+
+    ///Get the amount of particles the system can hold before it reallocates.
+    pub fn capacity(&self) -> usize {
+        self.particles.capacity()
+    }
+
+    ///Get the amount of particles in the system.
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    ///Check if the system has no particles.
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    ///Iterate over the particles in the system.
+    pub fn iter(&self) -> slice::Iter<'_, P> {
+        self.particles.iter()
+    }
+
+    ///Iterate mutably over the particles in the system.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, P> {
+        self.particles.iter_mut()
+    }
+
+    ///Get the particle at index i, or None if it is out of bounds.
+    pub fn get(&self, i: usize) -> Option<&P> {
+        self.particles.get(i)
+    }
+
+    ///Get the particle at index i mutably, or None if it is out of bounds.
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut P> {
+        self.particles.get_mut(i)
+    }
+
+    ///Keep only the particles for which f returns true. The remaining particles keep their order.
+    pub fn retain<F>(&mut self, f: F)
+        where F: FnMut(&P) -> bool
+    {
+        self.particles.retain(f);
+    }
+
+    ///Same as retain, but f can change the particles while deciding whether to keep them.
+    pub fn retain_mut<F>(&mut self, f: F)
+        where F: FnMut(&mut P) -> bool
+    {
+        self.particles.retain_mut(f);
+    }
+
+    ///Call f with disjoint mutable references to every unordered pair of particles exactly once.
+    ///
+    ///Pairs are visited as (i, j) with i < j in order. This can't be an Iterator, since every pair borrows the same
+    ///particles as other pairs and an Iterator lets all of its items be held at once.
+    pub fn for_each_pair_mut<F>(&mut self, mut f: F)
+        where F: FnMut(&mut P, &mut P)
+    {
+        for i in 0..self.particles.len() {
+            let (head, tail) = self.particles.split_at_mut(i + 1);
+            let lhs = &mut head[i];
+            for rhs in tail {
+                f(lhs, rhs);
+            }
+        }
+    }
+}
+
+///The default system is empty, the same as new.
+impl<P, V, D> Default for ParticleSystem<P, V, D> {
+    fn default() -> Self {
+        ParticleSystem::new()
+    }
+}
+
+impl<P, V, D> Index<usize> for ParticleSystem<P, V, D> {
+    type Output = P;
+    fn index(&self, i: usize) -> &P {
+        &self.particles[i]
+    }
+}
+
+impl<P, V, D> IndexMut<usize> for ParticleSystem<P, V, D> {
+    fn index_mut(&mut self, i: usize) -> &mut P {
+        &mut self.particles[i]
+    }
+}
+
+impl<P, V, D> Extend<P> for ParticleSystem<P, V, D> {
+    ///Add every particle from an iterator to the system.
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item=P>
+    {
+        self.particles.extend(iter);
+    }
+}
+
+impl<P, V, D> ParticleSystem<P, V, D>
+    where P: PhysicsParticle<V, D>, V: Vector<D>, D: Float
+{
+    ///Advance every particle in the system forward in time.
+    pub fn advance(&mut self, time: D) {
+        for particle in &mut self.particles {
+            particle.advance(time);
+        }
+    }
+
+    ///Run forces to accumulate every force of the step and then advance every particle once.
+    ///
+    ///Forces can layer as many passes as needed, like gravity between pairs, drag, and springs, as long as they only
+    ///apply forces through impulse and leave advancing to this step.
+    pub fn step<F>(&mut self, mut forces: F, dt: D)
+        where F: FnMut(&mut Self)
+    {
+        forces(self);
+        self.advance(dt);
+    }
+
+    ///Gravitate every unique pair of particles together and then advance them forward in time.
+    ///
+    ///In debug builds this also steps copies of the particles without their pending forces and asserts that gravity
+    ///left their total momentum unchanged, which catches third-law violations where the two bodies of a pair don't
+    ///get opposite forces. That makes a debug step_gravity do twice the O(n^2) work and allocate a copy of every
+    ///particle each step.
+    pub fn step_gravity(&mut self, magnitude: D, time: D) {
+        #[cfg(debug_assertions)]
+        self.debug_assert_gravity_conserves_momentum(magnitude, time);
+        for (i, lhs) in self.particles.iter().enumerate() {
+            for rhs in &self.particles[i + 1..] {
+                gravitate(lhs, rhs, magnitude);
+            }
+        }
+        self.advance(time);
+    }
+
+    #[cfg(debug_assertions)]
+    fn debug_assert_gravity_conserves_momentum(&self, magnitude: D, time: D) {
+        let mut copies: Vec<_> = self.particles.iter().map(|p| p.basic_form()).collect();
+        let before = total_momentum(copies.iter());
+        for (i, lhs) in copies.iter().enumerate() {
+            for rhs in &copies[i + 1..] {
+                gravitate(lhs, rhs, magnitude);
+            }
+        }
+        for copy in &mut copies {
+            copy.advance(time);
+        }
+        let scale = copies.iter().fold(before.displacement(), |scale, p| scale + p.momentum().displacement());
+        debug_assert!((total_momentum(copies.iter()) - before).displacement() <= scale * D::epsilon().sqrt(),
+            "gravitate changed the total momentum of the system");
+    }
+
+    ///Apply hooke_equilibrium along every edge (i, j, equilibrium) and then advance the particles forward in time.
+    ///
+    ///Springs usually form a sparse graph, so only the listed pairs interact. Every edge is checked before any force
+    ///is applied, so this fails without modifying the system if an index is out of range or an edge joins a
+    ///particle to itself.
+    pub fn step_hooke(&mut self, edges: &[(usize, usize, D)], magnitude: D, time: D) -> Result<(), SimError> {
+        let len = self.particles.len();
+        for &(i, j, _) in edges {
+            if i >= len || j >= len {
+                return Err(SimError::EdgeOutOfRange(i, j));
+            }
+            if i == j {
+                return Err(SimError::SelfEdge(i));
+            }
+        }
+        for &(i, j, equilibrium) in edges {
+            hooke_equilibrium(&self.particles[i], &self.particles[j], equilibrium, magnitude);
+        }
+        self.advance(time);
+        Ok(())
+    }
+
+    ///Apply lennard_jones between every pair of particles no further than cutoff apart and then advance them forward
+    ///in time.
+    ///
+    ///The pairs are found with a SpatialHash whose cells are cutoff wide, so only nearby particles are compared. The
+    ///force beyond a few sigma is tiny, so a cutoff around 2.5 sigma is common. Pairs are applied in sorted order so
+    ///the result doesn't depend on the order of the hash. This panics if cutoff is not positive and finite.
+    pub fn step_lennard_jones(&mut self, epsilon: D, sigma: D, cutoff: D, time: D) {
+        assert!(cutoff > D::zero() && cutoff.is_finite(), "step_lennard_jones needs a positive and finite cutoff");
+        let mut pairs: Vec<_> = SpatialHash::new(&self.particles, cutoff).neighbor_pairs(cutoff).collect();
+        pairs.sort();
+        for (i, j) in pairs {
+            lennard_jones(&self.particles[i], &self.particles[j], epsilon, sigma);
+        }
+        self.advance(time);
+    }
+
+    ///Gravitate every particle to a single center of attraction and then advance them forward in time.
+    ///
+    ///The center is not moved and the particles do not attract each other, which is linear in the amount of
+    ///particles and suits systems where one body dominates.
+    pub fn gravitate_all_to<C: ?Sized>(&mut self, center: &C, magnitude: D, dt: D)
+        where C: Quanta<D> + Position<V>
+    {
+        for particle in &self.particles {
+            particle.gravitate_to(center, magnitude);
+        }
+        self.advance(dt);
+    }
+
+    ///Gravitate every particle together and advance them by a time step small enough that none of them moves more
+    ///than max_disp, but never by more than max_dt; the time step that was taken is returned.
+    ///
+    ///Over a step dt a particle moves at most |v|dt + |a|dt^2, so dt is the smallest positive root of that bound for
+    ///any particle. Forces applied through impulse before the call are advanced too, but are not part of the bound.
+    pub fn step_adaptive(&mut self, magnitude: D, max_dt: D, max_disp: D) -> D {
+        let accelerations = self.gravity_accelerations(magnitude);
+        let two = D::one() + D::one();
+        let dt = self.particles.iter().zip(accelerations.iter()).fold(max_dt, |dt, (p, a)| {
+            let (v, a) = (p.velocity().displacement(), a.displacement());
+            dt.min(two * max_disp / (v + (v * v + two * two * a * max_disp).sqrt()))
+        });
+        for (particle, accel) in self.particles.iter().zip(accelerations) {
+            particle.impulse(&(accel * particle.inertia()));
+        }
+        self.advance(dt);
+        dt
+    }
+
+    ///Compute the kinetic energy plus the gravitational potential energy of every unique pair of particles.
+    pub fn gravitational_energy(&self, magnitude: D) -> D {
+        let mut energy = total_kinetic_energy(self.particles.iter());
+        for (i, lhs) in self.particles.iter().enumerate() {
+            for rhs in &self.particles[i + 1..] {
+                energy = energy + gravitational_potential(lhs, rhs, magnitude);
+            }
+        }
+        energy
+    }
+
+    ///Compute the smallest axis aligned Box holding every particle, or None if the system is empty.
+    pub fn bounding_box(&self) -> Option<Box<V>> {
+        let mut positions = self.particles.iter().map(|p| p.position());
+        positions.next().map(|first| {
+            let (min, max) = positions.fold((first, first), |(min, max), p| {
+                (min.zip_map(&p, |a, b| a.min(b)), max.zip_map(&p, |a, b| a.max(b)))
+            });
+            Box::from_corners(min, max)
+        })
+    }
+
+    ///Compute a sphere holding every particle as its center and radius, or None if the system is empty.
+    ///
+    ///The center is the center of the bounding_box, so the sphere is cheap to find but not always the smallest one.
+    pub fn bounding_sphere(&self) -> Option<(V, D)> {
+        self.bounding_box().map(|bounds| {
+            let radius = self.particles.iter()
+                .fold(D::zero(), |radius, p| radius.max((p.position() - bounds.origin).displacement()));
+            (bounds.origin, radius)
+        })
+    }
+
+    ///Remove every particle further than max_radius from center and return how many were removed.
+    ///
+    ///The remaining particles keep their order.
+    pub fn remove_escaped(&mut self, center: &V, max_radius: D) -> usize {
+        let len = self.particles.len();
+        let max_squared = max_radius * max_radius;
+        self.particles.retain(|p| (p.position() - *center).displacement_squared() <= max_squared);
+        len - self.particles.len()
+    }
+
+    ///Compute the average position of the particles weighted by their inertia, or None if the system is empty.
+    pub fn center_of_mass(&self) -> Option<V> {
+        if self.particles.is_empty() {
+            return None;
+        }
+        let (weighted, mass) = self.particles.iter()
+            .fold((V::zero(), D::zero()), |(w, m), p| (w + p.position() * p.inertia(), m + p.inertia()));
+        Some(weighted / mass)
+    }
+
+    ///Compute the acceleration gravitate_to would give a test particle with unit quanta and inertia at point.
+    ///
+    ///Particles exactly at point are skipped, the same as gravitate_to skips a coincident center.
+    pub fn field_at(&self, point: &V, magnitude: D) -> V {
+        self.particles.iter().fold(V::zero(), |field, p| {
+            let delta = p.position() - *point;
+            let distance = delta.displacement();
+            if distance.is_normal() {
+                field + delta / distance.powi(3) * magnitude * p.quanta()
+            } else {
+                field
+            }
+        })
+    }
+
+    ///Compute the gravitational potential of a test particle with unit quanta at point.
+    pub fn potential_at(&self, point: &V, magnitude: D) -> D {
+        self.particles.iter().fold(D::zero(), |potential, p| {
+            let distance = (p.position() - *point).displacement();
+            if distance.is_normal() {
+                potential - magnitude * p.quanta() / distance
+            } else {
+                potential
+            }
+        })
+    }
+
+    ///Copy out the position, quanta, and inertia of every particle.
+    fn bodies(&self) -> Vec<(V, D, D)> {
+        self.particles.iter().map(|p| (p.position(), p.quanta(), p.inertia())).collect()
+    }
+
+    ///Compute the acceleration gravitate would give every particle from all of the others without applying it.
+    pub fn gravity_accelerations(&self, magnitude: D) -> Vec<V> {
+        let bodies = self.bodies();
+        (0..bodies.len()).map(|i| gravity_acceleration(&bodies, i, magnitude)).collect()
+    }
+
+    ///Same as gravity_accelerations, but each particle is computed in parallel; the results are identical.
+    #[cfg(feature = "rayon")]
+    pub fn gravity_accelerations_parallel(&self, magnitude: D) -> Vec<V>
+        where V: Send + Sync, D: Send + Sync
+    {
+        let bodies = self.bodies();
+        (0..bodies.len()).into_par_iter().map(|i| gravity_acceleration(&bodies, i, magnitude)).collect()
+    }
+
+    ///Same as step_gravity, but the accelerations are computed in parallel before being applied to the particles.
+    #[cfg(feature = "rayon")]
+    pub fn step_gravity_parallel(&mut self, magnitude: D, time: D)
+        where V: Send + Sync, D: Send + Sync
+    {
+        let accelerations = self.gravity_accelerations_parallel(magnitude);
+        for (particle, accel) in self.particles.iter().zip(accelerations) {
+            particle.impulse(&(accel * particle.inertia()));
+        }
+        self.advance(time);
+    }
+}
+
+impl<P, V, D> ParticleSystem<P, V, D>
+    where P: PhysicsParticle<V, D> + PositionMut<V> + VelocityMut<V>, V: Vector<D>, D: Float
+{
+    ///Move every particle into the center of mass frame, where the center of mass is at the origin and at rest.
+    ///
+    ///An empty system is left alone.
+    pub fn recenter(&mut self) {
+        let center = match self.center_of_mass() {
+            Some(center) => center,
+            None => return,
+        };
+        let mass = self.particles.iter().fold(D::zero(), |m, p| m + p.inertia());
+        let velocity = total_momentum(self.particles.iter()) / mass;
+        for particle in &mut self.particles {
+            let (position, v) = (particle.position(), particle.velocity());
+            particle.set_position(position - center);
+            particle.set_velocity(v - velocity);
+        }
+    }
+
+    ///Negate the velocity of every particle, which runs a time-reversible integrator backwards along its path.
+    pub fn reverse_velocities(&mut self) {
+        for particle in &mut self.particles {
+            let velocity = particle.velocity();
+            particle.set_velocity(-velocity);
+        }
+    }
+
+    ///Scale every velocity by sqrt(target / current) so that the total kinetic energy becomes target.
+    ///
+    ///If every particle is at rest there is no direction to scale along, so the velocities are left unchanged. A
+    ///negative target is clamped to zero, which brings every particle to rest.
+    pub fn rescale_to_kinetic_energy(&mut self, target: D) {
+        let current = total_kinetic_energy(self.particles.iter());
+        if current.is_normal() {
+            let scale = (target.max(D::zero()) / current).sqrt();
+            for particle in &mut self.particles {
+                let velocity = particle.velocity();
+                particle.set_velocity(velocity * scale);
+            }
+        }
+    }
+}
+
+impl<V, D> ParticleSystem<BallParticle<V, D>, V, D>
+    where V: Vector<D>, D: Float
+{
+    ///Merge every pair of overlapping balls with BallParticle::merge and return the amount of merges.
+    ///
+    ///The merged ball replaces the one with the lower index and the other is removed with swap_remove, so the order
+    ///of the particles changes. A merged ball is bigger and may now overlap balls that were already checked, so the
+    ///scan starts over after every merge until no overlapping pair is left. This is quadratic per merge, which is
+    ///fine while merges are rare compared to steps.
+    pub fn merge_collisions(&mut self) -> usize {
+        let mut merges = 0;
+        while let Some((i, j)) = self.overlapping_pair() {
+            let other = self.particles.swap_remove(j);
+            self.particles[i] = self.particles[i].merge(&other);
+            merges += 1;
+        }
+        merges
+    }
+
+    fn overlapping_pair(&self) -> Option<(usize, usize)> {
+        for (i, lhs) in self.particles.iter().enumerate() {
+            for (j, rhs) in self.particles.iter().enumerate().skip(i + 1) {
+                if (rhs.position() - lhs.position()).displacement_squared() < (lhs.radius + rhs.radius).powi(2) {
+                    return Some((i, j));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<V, D> ParticleSystem<BasicParticle<V, D>, V, D>
+    where V: Vector<D>, D: Float
+{
+    ///Same as step_gravity, but every particle is advanced with the kick-drift-kick leapfrog method.
+    ///
+    ///The accelerations are computed twice per step, once at the start and once at the drifted positions. Since
+    ///each step only depends on the positions and velocities, stepping N times, calling reverse_velocities, and
+    ///stepping N times again returns to the initial positions up to rounding.
+    pub fn step_gravity_leapfrog(&mut self, magnitude: D, time: D) {
+        let before = self.gravity_accelerations(magnitude);
+        let mut bodies = self.bodies();
+        for ((body, particle), accel) in bodies.iter_mut().zip(self.particles.iter()).zip(before.iter()) {
+            body.0 = particle.leapfrog_position(accel, time);
+        }
+        for (i, (particle, accel)) in self.particles.iter_mut().zip(before.iter()).enumerate() {
+            particle.advance_leapfrog(accel, &gravity_acceleration(&bodies, i, magnitude), time);
+        }
+    }
+}
+
+///Sum the gravitational acceleration of body i from every other body.
+fn gravity_acceleration<V, D>(bodies: &[(V, D, D)], i: usize, magnitude: D) -> V
+    where V: Vector<D>, D: Float
+{
+    let (position, quanta, inertia) = bodies[i];
+    let mut accel = V::zero();
+    for (j, &(other, other_quanta, _)) in bodies.iter().enumerate() {
+        let delta = other - position;
+        let distance = delta.displacement();
+        if j != i && distance.is_normal() {
+            accel = accel + delta / distance.powi(3) * other_quanta;
+        }
+    }
+    accel * magnitude * quanta / inertia
+}
+
+//A small linear congruential generator that gives numbers in [0, 1) so that tests are deterministic.
+#[cfg(test)]
+fn test_random(mut seed: u64) -> impl FnMut() -> f64 {
+    move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[test]
+fn step_gravity_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut system = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(0.1, 0.0, 0.0), 1.0));
+    system.push(P::new(2.0, Cartesian3::new(1.0, 0.0, 0.0), Cartesian3::new(0.0, 0.3, 0.0), 2.0));
+    system.push(P::new(0.5, Cartesian3::new(0.0, 1.0, 0.5), Cartesian3::new(0.0, 0.0, -0.2), 0.5));
+    assert_eq!(system.len(), 3);
+    let momentum = |system: &ParticleSystem<P, _, _>| system.iter()
+        .fold(Cartesian3::new(0.0, 0.0, 0.0), |acc, p| acc + p.velocity * p.inertia);
+    let start = momentum(&system);
+    for _ in 0..10 {
+        system.step_gravity(1.0, 0.01);
+    }
+    assert!((momentum(&system) - start).displacement() < 1e-12);
+}
+
+#[test]
+fn step_hooke_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(2.5, 0.5), Cartesian2::new(0.0, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(1.0, 0.5), Cartesian2::new(0.0, 0.0), 1.0));
+    //A 3-4-5 right triangle.
+    let edges = [(0, 1, 3.0), (1, 2, 4.0), (2, 0, 5.0)];
+    for _ in 0..10000 {
+        for p in system.iter() {
+            p.drag(1.0);
+        }
+        system.step_hooke(&edges, 2.0, 0.01).unwrap();
+    }
+    let p: Vec<_> = system.iter().collect();
+    for &(i, j, equilibrium) in &edges {
+        assert!(((p[j].position - p[i].position).displacement() - equilibrium).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn step_hooke_invalid_edge_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    //The valid edge before the bad one must not be applied either.
+    assert_eq!(system.step_hooke(&[(0, 1, 2.0), (0, 2, 1.0)], 1.0, 0.01), Err(SimError::EdgeOutOfRange(0, 2)));
+    assert_eq!(system.step_hooke(&[(1, 1, 1.0)], 1.0, 0.01), Err(SimError::SelfEdge(1)));
+    assert!(system.iter().all(|p| p.velocity.x == 0.0 && p.net_force().x == 0.0));
+}
+
+#[test]
+fn step_lennard_jones_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut random = test_random(7);
+    //A jittered lattice keeps the particles from starting on top of each other.
+    let particles: Vec<P> = (0..100).map(|i| {
+        let (x, y) = ((i % 10) as f64 * 1.2, (i / 10) as f64 * 1.2);
+        P::new(1.0, Cartesian2::new(x + random() * 0.2, y + random() * 0.2), Cartesian2::new(0.0, 0.0), 1.0)
+    }).collect();
+    let (epsilon, sigma, cutoff) = (1.0, 1.0, 2.5);
+    let mut brute = particles.clone();
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.extend(particles);
+    for _ in 0..10 {
+        system.step_lennard_jones(epsilon, sigma, cutoff, 0.001);
+        for i in 0..brute.len() {
+            for j in i + 1..brute.len() {
+                if (brute[j].position - brute[i].position).displacement() <= cutoff {
+                    lennard_jones(&brute[i], &brute[j], epsilon, sigma);
+                }
+            }
+        }
+        for p in &mut brute {
+            p.advance(0.001);
+        }
+    }
+    for (s, b) in system.iter().zip(brute.iter()) {
+        assert_eq!((s.position.x, s.position.y), (b.position.x, b.position.y));
+    }
+    //The particles did interact.
+    assert!(system.iter().any(|p| p.velocity.displacement() > 1e-3));
+}
+
+#[test]
+#[should_panic(expected = "positive and finite cutoff")]
+fn step_lennard_jones_zero_cutoff_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::default());
+    system.step_lennard_jones(1.0, 1.0, 0.0, 0.01);
+}
+
+#[test]
+fn lennard_jones_settling_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let (epsilon, sigma) = (1.0, 1.0);
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    //A loose square lattice collapses into a close packed cluster.
+    for i in 0..25 {
+        let (x, y) = ((i % 5) as f64 * 1.5, (i / 5) as f64 * 1.5 + (i % 2) as f64 * 0.1);
+        system.push(P::new(1.0, Cartesian2::new(x, y), Cartesian2::new(0.0, 0.0), 1.0));
+    }
+    for _ in 0..4000 {
+        for p in system.iter() {
+            p.drag(1.0);
+        }
+        system.step_lennard_jones(epsilon, sigma, 2.5 * sigma, 0.01);
+    }
+    let mut nearest: Vec<f64> = system.iter().map(|p| system.iter()
+        .map(|q| (q.position - p.position).displacement())
+        .filter(|&d| d > 0.0)
+        .fold(f64::INFINITY, f64::min)).collect();
+    nearest.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    //The median nearest neighbor sits near the minimum of the potential at 2^(1/6) sigma.
+    let minimum = 2.0f64.powf(1.0 / 6.0) * sigma;
+    assert!((nearest[nearest.len() / 2] - minimum).abs() < 0.03 * minimum);
+}
+
+#[test]
+fn step_gravity_third_law_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(3.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 3.0));
+    system.push(P::new(1.0, Cartesian2::new(1.0, 1.0), Cartesian2::new(0.0, 0.0), 1.0));
+    system.step_gravity(1.0, 0.1);
+    let p: Vec<_> = system.iter().collect();
+    //Each particle moves towards the other and their momenta cancel.
+    assert!(p[0].velocity.x > 0.0 && p[0].velocity.y > 0.0);
+    assert!(p[1].velocity.x < 0.0 && p[1].velocity.y < 0.0);
+    assert!((p[0].momentum() + p[1].momentum()).displacement() < 1e-15);
+}
+
+#[test]
+fn index_mut_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(5.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    system[1].velocity = Cartesian2::new(0.0, 2.0);
+    system.get_mut(0).unwrap().velocity.x = -1.0;
+    for p in system.iter_mut() {
+        p.position.y = 1.0;
+    }
+    system.advance(0.5);
+    assert_eq!((system[0].position.x, system[0].position.y), (-0.5, 1.0));
+    assert_eq!((system[1].position.x, system[1].position.y), (5.0, 2.0));
+    assert!(system.get(2).is_none());
+}
+
+#[test]
+fn retain_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    for &x in &[-2.0, 1.0, -0.5, 3.0, 0.0] {
+        system.push(P::new(1.0, Cartesian2::new(x, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    }
+    system.retain(|p| p.position.x >= 0.0);
+    let xs: Vec<_> = system.iter().map(|p| p.position.x).collect();
+    assert_eq!(xs, vec![1.0, 3.0, 0.0]);
+    //Send the survivors back towards the origin while dropping the particle already there.
+    system.retain_mut(|p| {
+        p.velocity.x = -p.position.x;
+        p.position.x != 0.0
+    });
+    let vs: Vec<_> = system.iter().map(|p| p.velocity.x).collect();
+    assert_eq!(vs, vec![-1.0, -3.0]);
+}
+
+#[test]
+fn step_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(2.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    let field = Cartesian2::new(0.0, -10.0);
+    system.step(|system| {
+        for (i, lhs) in system.iter().enumerate() {
+            for rhs in system.iter().skip(i + 1) {
+                gravitate(lhs, rhs, 4.0);
+            }
+        }
+        for p in system.iter() {
+            p.apply_uniform_field(&field);
+        }
+    }, 0.5);
+    //Gravity pulls the particles together along x while the field pulls both down along y.
+    assert_eq!((system[0].velocity.x, system[0].velocity.y), (0.5, -5.0));
+    assert_eq!((system[1].velocity.x, system[1].velocity.y), (-0.5, -5.0));
+}
+
+#[test]
+fn extend_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::with_capacity(1000);
+    let capacity = system.capacity();
+    assert!(capacity >= 1000);
+    system.extend((0..1000).map(|i| P::new(1.0, Cartesian2::new(i as f64, 0.0), Cartesian2::new(0.0, 0.0), 1.0)));
+    assert_eq!(system.len(), 1000);
+    assert_eq!(system.capacity(), capacity);
+    system.reserve(500);
+    assert!(system.capacity() >= 1500);
+}
+
+#[test]
+fn bounding_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian3<f64>, f64> = ParticleSystem::new();
+    assert!(system.bounding_box().is_none() && system.bounding_sphere().is_none());
+    for &(x, y, z) in &[(1.0, -2.0, 0.5), (-3.0, 4.0, 0.0), (2.0, 1.0, -1.5)] {
+        system.push(P::new(1.0, Cartesian3::new(x, y, z), Cartesian3::new(0.0, 0.0, 0.0), 1.0));
+    }
+    let bounds = system.bounding_box().unwrap();
+    let (min, max) = (bounds.origin - bounds.offset, bounds.origin + bounds.offset);
+    assert_eq!((min.x, min.y, min.z), (-3.0, -2.0, -1.5));
+    assert_eq!((max.x, max.y, max.z), (2.0, 4.0, 0.5));
+    let (center, radius) = system.bounding_sphere().unwrap();
+    assert_eq!((center.x, center.y, center.z), (-0.5, 1.0, -0.5));
+    assert!(system.iter().all(|p| (p.position - center).displacement() <= radius));
+    assert!((radius - 2.5f64.hypot(3.0).hypot(0.5)).abs() < 1e-12);
+}
+
+#[test]
+fn remove_escaped_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    for &(x, y) in &[(1.0, 0.0), (100.0, 0.0), (-3.0, 4.0), (0.0, -50.0), (0.5, 0.5)] {
+        system.push(P::new(1.0, Cartesian2::new(x, y), Cartesian2::new(0.0, 0.0), 1.0));
+    }
+    assert_eq!(system.remove_escaped(&Cartesian2::new(0.0, 0.0), 5.0), 2);
+    let left: Vec<_> = system.iter().map(|p| (p.position.x, p.position.y)).collect();
+    assert_eq!(left, vec![(1.0, 0.0), (-3.0, 4.0), (0.5, 0.5)]);
+    assert_eq!(system.remove_escaped(&Cartesian2::new(0.0, 0.0), 5.0), 0);
+}
+
+#[test]
+fn recenter_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(1.0, 2.0), Cartesian2::new(0.5, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(4.0, -1.0), Cartesian2::new(0.0, 1.0), 3.0));
+    assert!((system.center_of_mass().unwrap() - Cartesian2::new(3.25, -0.25)).displacement() < 1e-12);
+    system.recenter();
+    assert!(system.center_of_mass().unwrap().displacement() < 1e-12);
+    assert!(total_momentum(system.iter()).displacement() < 1e-12);
+    //The separation is unchanged.
+    let p: Vec<_> = system.iter().collect();
+    assert!((p[1].position - p[0].position - Cartesian2::new(3.0, -3.0)).displacement() < 1e-12);
+    //An empty system has no center of mass and recentering it does nothing.
+    let mut empty: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    assert!(empty.center_of_mass().is_none());
+    empty.recenter();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn field_at_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut system = ParticleSystem::new();
+    system.push(P::new(2.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(0.0, 0.0, 0.0), 1.0));
+    system.push(P::new(0.5, Cartesian3::new(1.0, 1.0, 0.0), Cartesian3::new(0.0, 0.0, 0.0), 3.0));
+    system.push(P::new(1.0, Cartesian3::new(-2.0, 0.5, 1.0), Cartesian3::new(0.0, 0.0, 0.0), 1.0));
+    let point = Cartesian3::new(1.0, 1.0, 0.0) - Cartesian3::new(1e-3, 0.0, 0.0);
+    let field = system.field_at(&point, 0.5);
+
+    let mut probe = P::new(1.0, point, Cartesian3::new(0.0, 0.0, 0.0), 1.0);
+    for p in system.iter() {
+        probe.gravitate_to(p, 0.5);
+    }
+    probe.advance(1.0);
+    assert!((probe.velocity - field).displacement() <= 1e-12 * field.displacement());
+
+    let probe = P::new(1.0, point, Cartesian3::new(0.0, 0.0, 0.0), 1.0);
+    let expected = system.iter().fold(0.0, |acc, p| acc + gravitational_potential(&probe, p, 0.5));
+    assert!((system.potential_at(&point, 0.5) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn merge_collisions_test() {
+    type P = BallParticle<Cartesian3<f64>, f64>;
+    let mut system = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(1.0, 0.0, 0.0), 1.0, 0.5));
+    system.push(P::new(1.0, Cartesian3::new(0.8, 0.0, 0.0), Cartesian3::new(0.0, 2.0, 0.0), 1.0, 0.5));
+    system.push(P::new(1.0, Cartesian3::new(5.0, 0.0, 0.0), Cartesian3::new(0.0, 0.0, 0.0), 1.0, 0.5));
+    let momentum = total_momentum(system.iter());
+    assert_eq!(system.merge_collisions(), 1);
+    assert_eq!(system.len(), 2);
+    let mass = system.iter().fold(0.0, |acc, p| acc + p.inertia());
+    assert_eq!(mass, 3.0);
+    assert!((total_momentum(system.iter()) - momentum).displacement() < 1e-12);
+    let merged = system.iter().next().unwrap();
+    assert_eq!(merged.quanta(), 2.0);
+    assert!((merged.position() - Cartesian3::new(0.4, 0.0, 0.0)).displacement() < 1e-12);
+    assert!((merged.radius.powi(3) - 2.0 * 0.5f64.powi(3)).abs() < 1e-12);
+    assert_eq!(system.merge_collisions(), 0);
+}
+
+#[test]
+fn rescale_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(3.0, 4.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(-1.0, 0.5), 2.0));
+    let before: Vec<_> = system.iter().map(|p| p.velocity).collect();
+    system.rescale_to_kinetic_energy(2.0);
+    assert!((total_kinetic_energy(system.iter()) - 2.0).abs() < 1e-12);
+    for (p, v) in system.iter().zip(before) {
+        assert!((p.velocity.normalized() - v.normalized()).displacement() < 1e-12);
+    }
+
+    let mut still = ParticleSystem::new();
+    still.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+    still.rescale_to_kinetic_energy(2.0);
+    assert_eq!(still.iter().next().unwrap().velocity.x, 0.0);
+
+    system.rescale_to_kinetic_energy(-1.0);
+    assert!(system.iter().all(|p| p.velocity.displacement() == 0.0));
+}
+
+#[test]
+fn for_each_pair_mut_test() {
+    type P = BallParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    for i in 0..5 {
+        system.push(P::new(1.0, Cartesian2::new(i as f64 * 0.5, 0.0), Cartesian2::new(0.0, 0.0), 1.0, 0.3));
+    }
+    //The index of each particle is twice its x position.
+    let mut visited = Vec::new();
+    system.for_each_pair_mut(|a, b| visited.push((a.particle.position.x * 2.0, b.particle.position.x * 2.0)));
+    let expected: Vec<_> = (0..5).flat_map(|i| (i + 1..5).map(move |j| (i as f64, j as f64))).collect();
+    assert_eq!(visited, expected);
+
+    //Every particle is in four pairs.
+    system.for_each_pair_mut(|a, b| {
+        a.particle.quanta += 1.0;
+        b.particle.quanta += 1.0;
+    });
+    assert!(system.iter().all(|p| p.quanta() == 5.0));
+}
+
+#[test]
+fn gravitate_all_to_test() {
+    use std::f64::consts::PI;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let sun = P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0);
+    let mut system = ParticleSystem::new();
+    //Launched tangentially at the circular orbit speed, so a full orbit takes 2pi.
+    system.push(P::new(1.0, Cartesian2::new(1.0, 0.0), Cartesian2::new(0.0, 1.0), 1.0));
+    let steps = 10000;
+    for _ in 0..steps {
+        system.gravitate_all_to(&sun, 1.0, 2.0 * PI / steps as f64);
+        let p = system.iter().next().unwrap();
+        assert!((p.position.displacement() - 1.0).abs() < 0.01);
+    }
+    let p = system.iter().next().unwrap();
+    assert!((p.position - Cartesian2::new(1.0, 0.0)).displacement() < 0.01);
+}
+
+#[test]
+fn step_adaptive_test() {
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    //Two bodies falling straight into each other from rest, which would collide a little after 0.785.
+    let start = || {
+        let mut system = ParticleSystem::new();
+        system.push(P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(0.0, 0.0, 0.0), 1.0));
+        system.push(P::new(1.0, Cartesian3::new(1.0, 0.0, 0.0), Cartesian3::new(0.0, 0.0, 0.0), 1.0));
+        system
+    };
+    let separation = |system: &ParticleSystem<P, _, _>| {
+        let p: Vec<_> = system.iter().collect();
+        p[1].position.x - p[0].position.x
+    };
+
+    let mut fixed = start();
+    for _ in 0..3 {
+        fixed.step_gravity(1.0, 0.25);
+    }
+    assert!(separation(&fixed) < 0.0);
+
+    let mut adaptive = start();
+    let mut elapsed = 0.0;
+    while elapsed < 0.75 {
+        let before: Vec<_> = adaptive.iter().map(|p| p.position).collect();
+        let dt = adaptive.step_adaptive(1.0, (0.75 - elapsed).min(0.25), 0.01);
+        assert!(dt > 0.0 && dt <= 0.25);
+        for (p, b) in adaptive.iter().zip(before) {
+            assert!((p.position - b).displacement() <= 0.01 + 1e-12);
+        }
+        elapsed += dt;
+    }
+    assert!(separation(&adaptive) > 0.0);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn step_gravity_parallel_test() {
+    use self::num::Zero;
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let mut random = test_random(7);
+    let mut system = ParticleSystem::new();
+    for _ in 0..100 {
+        system.push(P::new(random(), Cartesian3::new(random(), random(), random()), Cartesian3::zero(), 1.0));
+    }
+    let serial = system.gravity_accelerations(1.0);
+    let parallel = system.gravity_accelerations_parallel(1.0);
+    for (s, p) in serial.iter().zip(parallel.iter()) {
+        assert!((*s - *p).is_zero());
+    }
+    //Stepping with the parallel accelerations matches stepping with gravitate.
+    let mut direct = ParticleSystem::new();
+    for p in system.iter() {
+        direct.push(p.clone());
+    }
+    system.step_gravity_parallel(1.0, 0.01);
+    direct.step_gravity(1.0, 0.01);
+    for (s, d) in system.iter().zip(direct.iter()) {
+        assert!((s.velocity - d.velocity).displacement() < 1e-9);
+    }
+}
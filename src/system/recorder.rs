@@ -0,0 +1,58 @@
+use super::ParticleSystem;
+use super::super::particle::*;
+
+///Records the positions of every particle in a ParticleSystem over time so they can be analyzed or played back.
+pub struct Recorder<V> {
+    frames: Vec<Vec<V>>,
+}
+
+impl<V> Recorder<V> {
+    pub fn new() -> Self {
+        Recorder{
+            frames: Vec::new(),
+        }
+    }
+
+    ///Append a frame with the current position of every particle in the order they were added to the system.
+    pub fn record<P, D>(&mut self, system: &ParticleSystem<P, V, D>)
+        where P: Position<V>
+    {
+        self.frames.push(system.iter().map(|p| p.position()).collect());
+    }
+
+    ///Get the positions from the frame recorded by call i to record.
+    pub fn frame(&self, i: usize) -> &[V] {
+        &self.frames[i]
+    }
+
+    ///Get the amount of frames that have been recorded.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+///The default recorder has no frames, the same as new.
+impl<V> Default for Recorder<V> {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+#[test]
+fn record_test() {
+    use super::super::vector::*;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(0.0, 1.0), Cartesian2::new(1.0, -0.5), 1.0));
+    let mut recorder = Recorder::new();
+    for _ in 0..3 {
+        recorder.record(&system);
+        system.advance(2.0);
+    }
+    assert_eq!(recorder.frame_count(), 3);
+    for i in 0..3 {
+        let frame = recorder.frame(i);
+        assert_eq!(frame.len(), 1);
+        assert_eq!((frame[0].x, frame[0].y), (2.0 * i as f64, 1.0 - i as f64));
+    }
+}
@@ -0,0 +1,127 @@
+extern crate num;
+use self::num::Float;
+use super::ParticleSystem;
+use super::super::vector::*;
+use super::super::particle::*;
+use super::super::error::SimError;
+
+///What happens to particles at the edge of a Simulation
+pub enum Boundary<V, D> {
+    ///Particles can move anywhere.
+    Open,
+    ///Particles wrap around the box and interact through its edges with the minimum image convention.
+    Periodic(Box<V>),
+    ///Particles bounce off of the walls of the box with the coefficient of restitution.
+    Walled(Box<V>, D),
+}
+
+///Holds the physical constants, time step, and boundary of a simulation so they don't have to be passed to every step.
+pub struct Simulation<V, D> {
+    ///The gravitational constant.
+    pub g: D,
+    ///The spring constant used by hooke_step.
+    pub spring_k: D,
+    ///The time step every step advances the particles by.
+    pub dt: D,
+    pub boundary: Boundary<V, D>,
+}
+
+impl<V, D> Simulation<V, D> {
+    pub fn new(g: D, spring_k: D, dt: D, boundary: Boundary<V, D>) -> Self {
+        Simulation{
+            g: g,
+            spring_k: spring_k,
+            dt: dt,
+            boundary: boundary,
+        }
+    }
+}
+
+impl<V, D> Simulation<V, D>
+    where V: Vector<D>, D: Float
+{
+    ///Gravitate every unique pair of particles together with g, advance them by dt, and apply the boundary.
+    ///
+    ///The boundary is only known when the step runs, so V needs a Box that can be both periodic and walled, even if
+    ///the boundary is open.
+    pub fn gravitate_step<P>(&self, system: &mut ParticleSystem<P, V, D>)
+        where P: PhysicsParticle<V, D> + PositionMut<V> + VelocityMut<V>, Box<V>: Toroid<V> + Walled<V, D>
+    {
+        match self.boundary {
+            Boundary::Periodic(ref space) => {
+                for (i, lhs) in system.particles.iter().enumerate() {
+                    for rhs in &system.particles[i + 1..] {
+                        gravitate_delta(lhs, rhs, self.g, |(l, r)| space.wrap_delta(r - l));
+                    }
+                }
+                system.advance(self.dt);
+            }
+            _ => system.step_gravity(self.g, self.dt),
+        }
+        self.apply_boundary(system);
+    }
+
+    ///Apply springs with spring_k along the edges like step_hooke, advance the particles by dt, and apply the
+    ///boundary. This fails without modifying the system if an edge is invalid, the same as step_hooke.
+    pub fn hooke_step<P>(&self, system: &mut ParticleSystem<P, V, D>, edges: &[(usize, usize, D)])
+        -> Result<(), SimError>
+        where P: PhysicsParticle<V, D> + PositionMut<V> + VelocityMut<V>, Box<V>: Toroid<V> + Walled<V, D>
+    {
+        system.step_hooke(edges, self.spring_k, self.dt)?;
+        self.apply_boundary(system);
+        Ok(())
+    }
+
+    fn apply_boundary<P>(&self, system: &mut ParticleSystem<P, V, D>)
+        where P: PositionMut<V> + VelocityMut<V>, Box<V>: Toroid<V> + Walled<V, D>
+    {
+        match self.boundary {
+            Boundary::Open => {}
+            Boundary::Periodic(ref space) => {
+                for particle in &mut system.particles {
+                    particle.wrap(space);
+                }
+            }
+            Boundary::Walled(ref space, restitution) => {
+                for particle in &mut system.particles {
+                    particle.reflect(space, restitution);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn scale_g_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let velocity = |g: f64| {
+        let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+        system.push(P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+        system.push(P::new(2.0, Cartesian2::new(2.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0));
+        let sim = Simulation::new(g, 0.0, 0.1, Boundary::Open);
+        sim.gravitate_step(&mut system);
+        system.iter().next().unwrap().velocity.x
+    };
+    //Vectors without a periodic or walled Box can still hold the constants.
+    let sim: Simulation<Cartesian4<f64>, f64> = Simulation::new(1.0, 0.0, 0.1, Boundary::Open);
+    assert_eq!(sim.g, 1.0);
+    let base = velocity(1.0);
+    assert!((base - 0.05).abs() < 1e-12);
+    assert!((velocity(3.0) - 3.0 * base).abs() < 1e-12);
+    assert!((velocity(0.5) - 0.5 * base).abs() < 1e-12);
+}
+
+#[test]
+fn periodic_boundary_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut system: ParticleSystem<P, Cartesian2<f64>, f64> = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian2::new(1.0, 5.0), Cartesian2::new(-20.0, 0.0), 1.0));
+    system.push(P::new(1.0, Cartesian2::new(9.0, 5.0), Cartesian2::new(0.0, 0.0), 1.0));
+    let space = Box::from_corners(Cartesian2::new(0.0, 0.0), Cartesian2::new(10.0, 10.0));
+    let sim = Simulation::new(1.0, 0.0, 0.1, Boundary::Periodic(space));
+    sim.gravitate_step(&mut system);
+    let p: Vec<_> = system.iter().collect();
+    //The nearest image of the second particle is 2 to the left of the first, so they attract through the edge.
+    assert!(p[0].velocity.x < -20.0 && p[1].velocity.x > 0.0);
+    assert!(p[0].position.x > 0.0 && p[0].position.x < 10.0);
+}
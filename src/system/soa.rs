@@ -0,0 +1,233 @@
+extern crate num;
+use self::num::Float;
+use super::super::vector::*;
+use super::super::particle::*;
+
+///Particles stored as parallel columns instead of a Vec of particle structs.
+///
+///Every column has one entry per particle, so the columns can be handed to code that wants flat buffers, like a
+///GPU upload, and kernels only touch the columns they need. Forces are accumulated in a private column by impulse and
+///the force kernels, then consumed by advance, the same as BasicParticle. The columns are only grown by push, so
+///they always have the same length; the accessors hand out slices that can be changed in place but not resized.
+pub struct ParticleSoa<V, D> {
+    quanta: Vec<D>,
+    charges: Vec<D>,
+    inertias: Vec<D>,
+    positions: Vec<V>,
+    velocities: Vec<V>,
+    forces: Vec<V>,
+}
+
+impl<V, D> ParticleSoa<V, D>
+    where V: Vector<D>, D: Float
+{
+    pub fn new() -> Self {
+        ParticleSoa{
+            quanta: Vec::new(),
+            charges: Vec::new(),
+            inertias: Vec::new(),
+            positions: Vec::new(),
+            velocities: Vec::new(),
+            forces: Vec::new(),
+        }
+    }
+
+    ///Copy the particles into columns; every charge is zero.
+    pub fn from_aos<P>(particles: &[P]) -> Self
+        where P: Particle<V, D> + Quanta<D>
+    {
+        let mut soa = ParticleSoa::new();
+        for p in particles {
+            soa.push(p.quanta(), D::zero(), p.position(), p.velocity(), p.inertia());
+        }
+        soa
+    }
+
+    ///Copy the charged particles into columns.
+    pub fn from_charged_aos(particles: &[ChargedParticle<V, D>]) -> Self {
+        let mut soa = ParticleSoa::new();
+        for p in particles {
+            soa.push(p.quanta(), p.charge, p.position(), p.velocity(), p.inertia());
+        }
+        soa
+    }
+
+    ///Copy the columns back out into particles, leaving out the charges and any forces that were not advanced.
+    pub fn to_aos(&self) -> Vec<BasicParticle<V, D>> {
+        (0..self.len())
+            .map(|i| BasicParticle::new(self.quanta[i], self.positions[i], self.velocities[i], self.inertias[i]))
+            .collect()
+    }
+
+    ///Copy the columns back out into charged particles, leaving out any forces that were not advanced.
+    pub fn to_charged_aos(&self) -> Vec<ChargedParticle<V, D>> {
+        (0..self.len())
+            .map(|i| ChargedParticle::new(self.quanta[i], self.charges[i], self.positions[i], self.velocities[i],
+                self.inertias[i]))
+            .collect()
+    }
+
+    ///Add a particle to the end of every column.
+    pub fn push(&mut self, quanta: D, charge: D, position: V, velocity: V, inertia: D) {
+        self.quanta.push(quanta);
+        self.charges.push(charge);
+        self.inertias.push(inertia);
+        self.positions.push(position);
+        self.velocities.push(velocity);
+        self.forces.push(V::zero());
+    }
+
+    ///The quanta of every particle.
+    pub fn quanta(&self) -> &[D] {
+        &self.quanta
+    }
+
+    pub fn quanta_mut(&mut self) -> &mut [D] {
+        &mut self.quanta
+    }
+
+    ///The charge of every particle.
+    pub fn charges(&self) -> &[D] {
+        &self.charges
+    }
+
+    pub fn charges_mut(&mut self) -> &mut [D] {
+        &mut self.charges
+    }
+
+    ///The inertia of every particle.
+    pub fn inertias(&self) -> &[D] {
+        &self.inertias
+    }
+
+    pub fn inertias_mut(&mut self) -> &mut [D] {
+        &mut self.inertias
+    }
+
+    ///The position of every particle.
+    pub fn positions(&self) -> &[V] {
+        &self.positions
+    }
+
+    pub fn positions_mut(&mut self) -> &mut [V] {
+        &mut self.positions
+    }
+
+    ///The velocity of every particle.
+    pub fn velocities(&self) -> &[V] {
+        &self.velocities
+    }
+
+    pub fn velocities_mut(&mut self) -> &mut [V] {
+        &mut self.velocities
+    }
+
+    ///Get the amount of particles.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    ///Check if there are no particles.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    ///Apply a force to particle i that is consumed by the next advance.
+    pub fn impulse(&mut self, i: usize, force: &V) {
+        self.forces[i] = self.forces[i] + *force;
+    }
+
+    ///Same as gravitate, applied to every unique pair of particles.
+    pub fn gravitate(&mut self, magnitude: D) {
+        for i in 0..self.len() {
+            for j in i + 1..self.len() {
+                let delta = self.positions[j] - self.positions[i];
+                let distance = delta.displacement();
+                if distance.is_normal() {
+                    let force = delta * magnitude / distance.powi(3) * self.quanta[i] * self.quanta[j];
+                    self.forces[i] = self.forces[i] + force;
+                    self.forces[j] = self.forces[j] - force;
+                }
+            }
+        }
+    }
+
+    ///Same as coulomb, applied to every unique pair of particles.
+    pub fn coulomb(&mut self, magnitude: D) {
+        for i in 0..self.len() {
+            for j in i + 1..self.len() {
+                let delta = self.positions[j] - self.positions[i];
+                let distance = delta.displacement();
+                if distance.is_normal() {
+                    let force = delta * magnitude / distance.powi(3) * self.charges[i] * self.charges[j];
+                    self.forces[i] = self.forces[i] - force;
+                    self.forces[j] = self.forces[j] + force;
+                }
+            }
+        }
+    }
+
+    ///Same as drag, applied to every particle.
+    pub fn drag(&mut self, magnitude: D) {
+        for (force, velocity) in self.forces.iter_mut().zip(self.velocities.iter()) {
+            *force = *force - *velocity * magnitude;
+        }
+    }
+
+    ///Same as apply_uniform_field, applied to every particle.
+    pub fn apply_uniform_field(&mut self, field: &V) {
+        for (force, &inertia) in self.forces.iter_mut().zip(self.inertias.iter()) {
+            *force = *force + *field * inertia;
+        }
+    }
+
+    ///Advance every particle forward in time and clear the forces, the same as BasicParticle::advance.
+    pub fn advance(&mut self, time: D) {
+        for i in 0..self.len() {
+            self.velocities[i] = self.velocities[i] + self.forces[i] / self.inertias[i] * time;
+            self.positions[i] = self.positions[i] + self.velocities[i] * time;
+            self.forces[i] = V::zero();
+        }
+    }
+
+    ///Same as ParticleSystem::step_gravity.
+    pub fn step_gravity(&mut self, magnitude: D, time: D) {
+        self.gravitate(magnitude);
+        self.advance(time);
+    }
+}
+
+///The default soa has no particles, the same as new.
+impl<V, D> Default for ParticleSoa<V, D>
+    where V: Vector<D>, D: Float
+{
+    fn default() -> Self {
+        ParticleSoa::new()
+    }
+}
+
+#[test]
+fn soa_matches_aos_test() {
+    use super::ParticleSystem;
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let particles = vec![
+        P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), Cartesian3::new(0.1, 0.0, 0.0), 1.0),
+        P::new(2.0, Cartesian3::new(1.0, 0.0, 0.0), Cartesian3::new(0.0, 0.3, 0.0), 2.0),
+        P::new(0.5, Cartesian3::new(0.0, 1.0, 0.5), Cartesian3::new(0.0, 0.0, -0.2), 0.5),
+    ];
+    let mut soa = ParticleSoa::from_aos(&particles);
+    let mut system: ParticleSystem<P, Cartesian3<f64>, f64> = ParticleSystem::new();
+    system.extend(particles);
+    for _ in 0..100 {
+        soa.step_gravity(1.0, 0.01);
+        system.step_gravity(1.0, 0.01);
+    }
+    let back = soa.to_aos();
+    assert_eq!(back.len(), 3);
+    assert_eq!((soa.positions().len(), soa.charges()[2]), (3, 0.0));
+    for (s, a) in back.iter().zip(system.iter()) {
+        assert!((s.position - a.position).displacement() < 1e-12);
+        assert!((s.velocity - a.velocity).displacement() < 1e-12);
+        assert_eq!((s.quanta, s.inertia), (a.quanta, a.inertia));
+    }
+}
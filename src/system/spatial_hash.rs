@@ -0,0 +1,228 @@
+extern crate num;
+use self::num::Float;
+use super::super::vector::*;
+use super::super::particle::*;
+use std::collections::HashMap;
+
+///A uniform grid that bins particles into cubic cells so that only particles in nearby cells are paired.
+///
+///The grid is a snapshot of the positions at construction and has to be updated after the particles move. Cells are
+///keyed by their integer coordinates, so any Vector works regardless of its dimensions.
+pub struct SpatialHash<V, D> {
+    cell_size: D,
+    positions: Vec<V>,
+    cells: HashMap<Vec<i64>, Vec<usize>>,
+}
+
+impl<V, D> SpatialHash<V, D>
+    where V: Vector<D>, D: Float
+{
+    ///Bin a snapshot of the particles into cells that are cell_size wide along every axis.
+    ///
+    ///Particles with a NaN or infinite coordinate are put into a sentinel cell, where they are never paired since
+    ///their distance to anything isn't finite. This panics if cell_size is not positive.
+    pub fn new<P>(particles: &[P], cell_size: D) -> Self
+        where P: Position<V>
+    {
+        assert!(cell_size > D::zero(), "SpatialHash needs a positive cell size");
+        let mut hash = SpatialHash{
+            cell_size: cell_size,
+            positions: particles.iter().map(|p| p.position()).collect(),
+            cells: HashMap::new(),
+        };
+        for i in 0..hash.positions.len() {
+            let cell = hash.cell(&hash.positions[i]);
+            hash.cells.entry(cell).or_default().push(i);
+        }
+        hash
+    }
+
+    ///Move the snapshot to the current positions of the particles, rebinning only those that changed cells, and
+    ///return how many did.
+    ///
+    ///Each particle that changes cells is searched for in its old cell, so this is cheapest when particles move little
+    ///compared to the cell size. Once a large share of the particles cross cells every step, or the cells are crowded,
+    ///building a new SpatialHash is cheaper. If the amount of particles changed, they can't be matched up with
+    ///the snapshot, so every particle is binned again.
+    pub fn update<P>(&mut self, particles: &[P]) -> usize
+        where P: Position<V>
+    {
+        if particles.len() != self.positions.len() {
+            *self = SpatialHash::new(particles, self.cell_size);
+            return particles.len();
+        }
+        let mut moved = 0;
+        for (i, particle) in particles.iter().enumerate() {
+            let position = particle.position();
+            let (old, new) = (self.cell(&self.positions[i]), self.cell(&position));
+            self.positions[i] = position;
+            if old != new {
+                let emptied = {
+                    let bodies = self.cells.get_mut(&old).unwrap();
+                    let index = bodies.iter().position(|&b| b == i).unwrap();
+                    bodies.swap_remove(index);
+                    bodies.is_empty()
+                };
+                if emptied {
+                    self.cells.remove(&old);
+                }
+                self.cells.entry(new).or_default().push(i);
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    ///The width of a cell along every axis.
+    pub fn cell_size(&self) -> D {
+        self.cell_size
+    }
+
+    //Coordinates that don't fit in an i64, like NaN and infinity, all go to the sentinel i64::MIN.
+    fn cell(&self, position: &V) -> Vec<i64> {
+        (0..V::dimensions()).map(|i| (position.get(i) / self.cell_size).floor().to_i64().unwrap_or(i64::MIN)).collect()
+    }
+
+    ///Find every pair of particle indices (i, j) with i < j that are no further than cutoff apart.
+    ///
+    ///Only cells within cutoff of each other are searched, so a cutoff close to the cell size is the cheapest. When
+    ///that would search more cells than there are particles, every pair of particles is checked instead. The pairs
+    ///are collected up front and come in no particular order. This panics if cutoff is NaN or infinite.
+    pub fn neighbor_pairs(&self, cutoff: D) -> impl Iterator<Item=(usize, usize)> {
+        assert!(cutoff.is_finite(), "SpatialHash::neighbor_pairs needs a finite cutoff");
+        let cutoff_squared = cutoff * cutoff;
+        let mut pairs = Vec::new();
+        if cutoff < D::zero() {
+            return pairs.into_iter();
+        }
+        let reach = (cutoff / self.cell_size).ceil().to_i64().unwrap_or(i64::MAX);
+        let searched = (reach.saturating_mul(2).saturating_add(1) as u64).checked_pow(V::dimensions() as u32);
+        if searched.is_none_or(|searched| searched > self.positions.len() as u64) {
+            for i in 0..self.positions.len() {
+                for j in i + 1..self.positions.len() {
+                    if (self.positions[j] - self.positions[i]).displacement_squared() <= cutoff_squared {
+                        pairs.push((i, j));
+                    }
+                }
+            }
+            return pairs.into_iter();
+        }
+
+        //Every offset in [-reach, reach] along every axis.
+        let mut offsets = vec![Vec::new()];
+        for _ in 0..V::dimensions() {
+            offsets = offsets.into_iter().flat_map(|o: Vec<i64>| (-reach..reach + 1).map(move |c| {
+                let mut o = o.clone();
+                o.push(c);
+                o
+            })).collect();
+        }
+
+        for (cell, bodies) in &self.cells {
+            for offset in &offsets {
+                let neighbor: Vec<i64> = cell.iter().zip(offset.iter()).map(|(c, o)| c.wrapping_add(*o)).collect();
+                if let Some(others) = self.cells.get(&neighbor) {
+                    //Each pair is seen from both of its cells, but only kept from the one holding the lower index.
+                    for &i in bodies {
+                        for &j in others.iter().filter(|&&j| i < j) {
+                            if (self.positions[j] - self.positions[i]).displacement_squared() <= cutoff_squared {
+                                pairs.push((i, j));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        pairs.into_iter()
+    }
+}
+
+#[test]
+fn neighbor_pairs_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut random = super::test_random(99);
+    let particles: Vec<P> = (0..300).map(|_| {
+        P::new(1.0, Cartesian2::new(random() * 20.0 - 10.0, random() * 20.0), Cartesian2::new(0.0, 0.0), 1.0)
+    }).collect();
+
+    //The last two search more cells than there are particles, so they check every pair instead.
+    for &(cell_size, cutoff) in &[(1.0, 1.0), (1.0, 2.5), (3.0, 1.0), (0.01, 1.0), (1.0, 1e30)] {
+        let mut brute = Vec::new();
+        for i in 0..particles.len() {
+            for j in i + 1..particles.len() {
+                if (particles[j].position - particles[i].position).displacement() <= cutoff {
+                    brute.push((i, j));
+                }
+            }
+        }
+        let mut pairs: Vec<_> = SpatialHash::new(&particles, cell_size).neighbor_pairs(cutoff).collect();
+        pairs.sort();
+        assert!(!brute.is_empty());
+        assert_eq!(pairs, brute);
+    }
+}
+
+#[test]
+fn update_test() {
+    use std::collections::BTreeMap;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut random = super::test_random(7);
+    let mut particles: Vec<P> = (0..500).map(|_| {
+        P::new(1.0, Cartesian2::new(random() * 20.0 - 10.0, random() * 20.0), Cartesian2::new(0.0, 0.0), 1.0)
+    }).collect();
+    let contents = |hash: &SpatialHash<Cartesian2<f64>, f64>| hash.cells.iter().map(|(cell, bodies)| {
+        let mut bodies = bodies.clone();
+        bodies.sort();
+        (cell.clone(), bodies)
+    }).collect::<BTreeMap<_, _>>();
+
+    let mut hash = SpatialHash::new(&particles, 1.0);
+    for _ in 0..10 {
+        for p in &mut particles {
+            p.position = p.position + Cartesian2::new(random() - 0.5, random() - 0.5) * 0.1;
+        }
+        let moved = hash.update(&particles);
+        assert!(moved > 0 && moved < particles.len() / 4);
+        let fresh = SpatialHash::new(&particles, 1.0);
+        assert_eq!(contents(&hash), contents(&fresh));
+        let (mut pairs, mut expected): (Vec<_>, Vec<_>) =
+            (hash.neighbor_pairs(1.0).collect(), fresh.neighbor_pairs(1.0).collect());
+        pairs.sort();
+        expected.sort();
+        assert_eq!(pairs, expected);
+    }
+    //A different amount of particles is binned from scratch.
+    particles.truncate(100);
+    assert_eq!(hash.update(&particles), 100);
+    assert_eq!(contents(&hash), contents(&SpatialHash::new(&particles, 1.0)));
+}
+
+#[test]
+fn non_finite_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut particles = vec![
+        P::new(1.0, Cartesian2::new(0.0, 0.0), Cartesian2::new(0.0, 0.0), 1.0),
+        P::new(1.0, Cartesian2::new(0.5, 0.0), Cartesian2::new(0.0, 0.0), 1.0),
+        P::new(1.0, Cartesian2::new(f64::NAN, 0.0), Cartesian2::new(0.0, 0.0), 1.0),
+        P::new(1.0, Cartesian2::new(f64::INFINITY, 0.0), Cartesian2::new(0.0, 0.0), 1.0),
+    ];
+    let mut hash = SpatialHash::new(&particles, 1.0);
+    assert_eq!(hash.neighbor_pairs(1.0).collect::<Vec<_>>(), vec![(0, 1)]);
+    particles[1].position.y = f64::NEG_INFINITY;
+    hash.update(&particles);
+    assert_eq!(hash.neighbor_pairs(1.0).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "positive cell size")]
+fn zero_cell_size_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    SpatialHash::new(&[P::default()], 0.0);
+}
+
+#[test]
+#[should_panic(expected = "finite cutoff")]
+fn infinite_cutoff_test() {
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    SpatialHash::new(&[P::default()], 1.0).neighbor_pairs(f64::INFINITY).count();
+}
@@ -1,9 +1,14 @@
 extern crate num;
-use super::Vector;
+use super::{Vector, Scalar};
 use self::num::{Float, Zero, FromPrimitive};
 use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::iter::Sum;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cartesian1<D> {
     pub x: D
 }
@@ -14,8 +19,19 @@ impl<D> Cartesian1<D> where D: Copy {
     }
 }
 
+///Formats as (x), where the precision and other flags apply to every component
+impl<D> fmt::Display for Cartesian1<D>
+    where D: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, ")")
+    }
+}
+
 impl<D> Zero for Cartesian1<D>
-    where D: Float
+    where D: Scalar
 {
     fn zero() -> Self {
         Cartesian1{x: D::zero()}
@@ -27,7 +43,7 @@ impl<D> Zero for Cartesian1<D>
 }
 
 impl<D> Add for Cartesian1<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
@@ -35,8 +51,28 @@ impl<D> Add for Cartesian1<D>
     }
 }
 
+impl<D> Sum for Cartesian1<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=Self>
+    {
+        iter.fold(Self::zero(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, D> Sum<&'a Cartesian1<D>> for Cartesian1<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=&'a Self>
+    {
+        iter.fold(Self::zero(), |acc, &v| acc + v)
+    }
+}
+
 impl<D> Sub for Cartesian1<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
@@ -45,7 +81,7 @@ impl<D> Sub for Cartesian1<D>
 }
 
 impl<D> Mul<D> for Cartesian1<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn mul(self, rhs: D) -> Self {
@@ -54,7 +90,7 @@ impl<D> Mul<D> for Cartesian1<D>
 }
 
 impl<D> Div<D> for Cartesian1<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn div(self, rhs: D) -> Self {
@@ -63,7 +99,7 @@ impl<D> Div<D> for Cartesian1<D>
 }
 
 impl<D> Neg for Cartesian1<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn neg(self) -> Self {
@@ -86,4 +122,30 @@ impl<D> Vector<D> for Cartesian1<D>
     fn displacement(&self) -> D {
         self.x
     }
+    fn displacement_squared(&self) -> D {
+        self.x * self.x
+    }
+    fn dimensions() -> usize {
+        1
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            _ => panic!("Cartesian1 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 1, "Cartesian1 needs a slice of length 1, but got {}", s.len());
+        Cartesian1{x: s[0]}
+    }
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        Cartesian1{x: f(self.x)}
+    }
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        Cartesian1{x: f(self.x, other.x)}
+    }
 }
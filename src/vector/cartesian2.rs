@@ -1,10 +1,15 @@
 extern crate num;
-use super::Vector;
+use super::{Vector, Scalar, PerpCross, Cartesian1};
 use self::num::{Float, Zero, FromPrimitive};
 use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::iter::Sum;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 use std::f64::consts::PI;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cartesian2<D> {
     pub x: D,
     pub y: D,
@@ -14,10 +19,48 @@ impl<D> Cartesian2<D> where D: Copy {
     pub fn new(x: D, y: D) -> Self {
         Cartesian2{x: x, y: y}
     }
+
+    ///Drop the y component to get a Cartesian1.
+    pub fn truncate(&self) -> Cartesian1<D> {
+        Cartesian1{x: self.x}
+    }
+}
+
+impl<D> Cartesian2<D> where D: Float {
+    ///Compute the vector rotated counterclockwise by angle in radians.
+    pub fn rotated(&self, angle: D) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Cartesian2{
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+}
+
+///Formats as (x, y), where the precision and other flags apply to every component
+impl<D> fmt::Display for Cartesian2<D>
+    where D: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, ")")
+    }
+}
+
+///Embed a Cartesian1 into Cartesian2 with the y component set to zero.
+impl<D> From<Cartesian1<D>> for Cartesian2<D>
+    where D: Scalar
+{
+    fn from(v: Cartesian1<D>) -> Self {
+        Cartesian2{x: v.x, y: D::zero()}
+    }
 }
 
 impl<D> Zero for Cartesian2<D>
-    where D: Float
+    where D: Scalar
 {
     fn zero() -> Self {
         Cartesian2{x: D::zero(), y: D::zero()}
@@ -29,7 +72,7 @@ impl<D> Zero for Cartesian2<D>
 }
 
 impl<D> Add for Cartesian2<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
@@ -37,8 +80,28 @@ impl<D> Add for Cartesian2<D>
     }
 }
 
+impl<D> Sum for Cartesian2<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=Self>
+    {
+        iter.fold(Self::zero(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, D> Sum<&'a Cartesian2<D>> for Cartesian2<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=&'a Self>
+    {
+        iter.fold(Self::zero(), |acc, &v| acc + v)
+    }
+}
+
 impl<D> Sub for Cartesian2<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
@@ -47,7 +110,7 @@ impl<D> Sub for Cartesian2<D>
 }
 
 impl<D> Mul<D> for Cartesian2<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn mul(self, rhs: D) -> Self {
@@ -56,7 +119,7 @@ impl<D> Mul<D> for Cartesian2<D>
 }
 
 impl<D> Div<D> for Cartesian2<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn div(self, rhs: D) -> Self {
@@ -65,7 +128,7 @@ impl<D> Div<D> for Cartesian2<D>
 }
 
 impl<D> Neg for Cartesian2<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn neg(self) -> Self {
@@ -91,4 +154,62 @@ impl<D> Vector<D> for Cartesian2<D>
     fn displacement_squared(&self) -> D {
         self.x * self.x + self.y * self.y
     }
+    fn dimensions() -> usize {
+        2
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("Cartesian2 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 2, "Cartesian2 needs a slice of length 2, but got {}", s.len());
+        Cartesian2{x: s[0], y: s[1]}
+    }
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        Cartesian2{x: f(self.x), y: f(self.y)}
+    }
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        Cartesian2{x: f(self.x, other.x), y: f(self.y, other.y)}
+    }
+}
+
+impl<D> PerpCross<D> for Cartesian2<D>
+    where D: Float
+{
+    fn perp_dot(lhs: &Self, rhs: &Self) -> D {
+        lhs.x * rhs.y - lhs.y * rhs.x
+    }
+}
+
+#[test]
+fn gravitate_cartesian2() {
+    use super::super::particle::*;
+    type P = BasicParticle<Cartesian2<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian2::new(-1.0, 0.5), Cartesian2::zero(), 1.0);
+    let mut b = P::new(1.0, Cartesian2::new(2.0, -1.5), Cartesian2::zero(), 1.0);
+    gravitate(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    //Equal inertia means the accelerations must be exactly opposite.
+    assert_eq!(a.velocity.x, -b.velocity.x);
+    assert_eq!(a.velocity.y, -b.velocity.y);
+    //Both particles must be pulled toward each other.
+    assert!(a.velocity.x > 0.0 && a.velocity.y < 0.0);
+}
+
+#[test]
+fn rotated_cartesian2() {
+    use std::f64::consts::FRAC_PI_2;
+    let r = Cartesian2::new(1.0, 0.0).rotated(FRAC_PI_2);
+    assert!((r - Cartesian2::new(0.0, 1.0)).displacement() < 1e-15);
+    let r = Cartesian2::new(3.0, -1.0).rotated(-0.7);
+    assert!((r.displacement() - Cartesian2::new(3.0, -1.0).displacement()).abs() < 1e-14);
+    assert!((r.rotated(0.7) - Cartesian2::new(3.0, -1.0)).displacement() < 1e-14);
 }
@@ -1,10 +1,15 @@
 extern crate num;
-use super::{Vector, CrossVector};
+use super::{Vector, Scalar, CrossVector, Cartesian2};
 use self::num::{Float, Zero, FromPrimitive};
 use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::iter::Sum;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
 use std::f64::consts::PI;
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cartesian3<D> {
     pub x: D,
     pub y: D,
@@ -15,10 +20,55 @@ impl<D> Cartesian3<D> where D: Copy {
     pub fn new(x: D, y: D, z: D) -> Self {
         Cartesian3{x: x, y: y, z: z}
     }
+
+    ///Drop the z component to get a Cartesian2.
+    pub fn truncate(&self) -> Cartesian2<D> {
+        Cartesian2{x: self.x, y: self.y}
+    }
+}
+
+impl<D> Cartesian3<D> where D: Float + FromPrimitive {
+    ///Rotate the vector by angle in radians around axis with Rodrigues' formula, counterclockwise when the axis
+    ///points at the viewer.
+    ///
+    ///The axis doesn't have to have a length of one, but a zero axis leaves the vector unchanged.
+    pub fn rotate_around(&self, axis: &Self, angle: D) -> Self {
+        let length = axis.displacement();
+        if !length.is_normal() {
+            return *self;
+        }
+        let axis = *axis / length;
+        let (sin, cos) = angle.sin_cos();
+        *self * cos + Self::cross(&axis, self) * sin + axis * (Self::dot(&axis, self) * (D::one() - cos))
+    }
+}
+
+///Formats as (x, y, z), where the precision and other flags apply to every component
+impl<D> fmt::Display for Cartesian3<D>
+    where D: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.z, f)?;
+        write!(f, ")")
+    }
+}
+
+///Embed a Cartesian2 into Cartesian3 with the z component set to zero.
+impl<D> From<Cartesian2<D>> for Cartesian3<D>
+    where D: Scalar
+{
+    fn from(v: Cartesian2<D>) -> Self {
+        Cartesian3{x: v.x, y: v.y, z: D::zero()}
+    }
 }
 
 impl<D> Zero for Cartesian3<D>
-    where D: Float
+    where D: Scalar
 {
     fn zero() -> Self {
         Cartesian3{x: D::zero(), y: D::zero(), z: D::zero()}
@@ -30,7 +80,7 @@ impl<D> Zero for Cartesian3<D>
 }
 
 impl<D> Add for Cartesian3<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
@@ -38,8 +88,28 @@ impl<D> Add for Cartesian3<D>
     }
 }
 
+impl<D> Sum for Cartesian3<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=Self>
+    {
+        iter.fold(Self::zero(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, D> Sum<&'a Cartesian3<D>> for Cartesian3<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=&'a Self>
+    {
+        iter.fold(Self::zero(), |acc, &v| acc + v)
+    }
+}
+
 impl<D> Sub for Cartesian3<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self {
@@ -48,7 +118,7 @@ impl<D> Sub for Cartesian3<D>
 }
 
 impl<D> Mul<D> for Cartesian3<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn mul(self, rhs: D) -> Self {
@@ -57,7 +127,7 @@ impl<D> Mul<D> for Cartesian3<D>
 }
 
 impl<D> Div<D> for Cartesian3<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn div(self, rhs: D) -> Self {
@@ -66,7 +136,7 @@ impl<D> Div<D> for Cartesian3<D>
 }
 
 impl<D> Neg for Cartesian3<D>
-    where D: Float
+    where D: Scalar
 {
     type Output = Self;
     fn neg(self) -> Self {
@@ -92,6 +162,31 @@ impl<D> Vector<D> for Cartesian3<D>
     fn displacement_squared(&self) -> D {
         self.x * self.x + self.y * self.y + self.z * self.z
     }
+    fn dimensions() -> usize {
+        3
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Cartesian3 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 3, "Cartesian3 needs a slice of length 3, but got {}", s.len());
+        Cartesian3{x: s[0], y: s[1], z: s[2]}
+    }
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        Cartesian3{x: f(self.x), y: f(self.y), z: f(self.z)}
+    }
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        Cartesian3{x: f(self.x, other.x), y: f(self.y, other.y), z: f(self.z, other.z)}
+    }
 }
 
 impl<D> CrossVector for Cartesian3<D>
@@ -105,3 +200,44 @@ impl<D> CrossVector for Cartesian3<D>
         }
     }
 }
+
+#[test]
+fn lorentz_cartesian3() {
+    use super::super::particle::*;
+    type P = BasicParticle<Cartesian3<f64>, f64>;
+    let va = Cartesian3::new(0.3, -1.0, 0.5);
+    let vb = Cartesian3::new(1.0, 0.2, -0.7);
+    let mut a = P::new(1.0, Cartesian3::new(0.0, 0.0, 0.0), va, 1.0);
+    let mut b = P::new(1.0, Cartesian3::new(1.0, 2.0, -1.0), vb, 1.0);
+    lorentz(&a, &b, 1.0);
+    //Unit inertia and time make the change in velocity equal to the force.
+    a.advance(1.0);
+    b.advance(1.0);
+    let force = b.velocity - vb;
+    assert!((a.velocity - va + force).displacement() < 1e-12);
+    //The magnetic force on a particle is perpendicular to the velocity of the source particle.
+    assert!(Cartesian3::dot(&force, &vb).abs() < 1e-12);
+
+    //A particle in a field is forced perpendicular to both its velocity and the field.
+    let field = Cartesian3::new(0.0, 0.4, 1.3);
+    let mut c = P::new(1.0, Cartesian3::zero(), va, 1.0);
+    c.lorentz_field(&field);
+    c.advance(1.0);
+    let force = c.velocity - va;
+    assert!(force.displacement() > 0.1);
+    assert!(Cartesian3::dot(&force, &va).abs() < 1e-12);
+    assert!(Cartesian3::dot(&force, &field).abs() < 1e-12);
+}
+
+#[test]
+fn rotate_around_cartesian3() {
+    use std::f64::consts::{PI, FRAC_PI_2};
+    let r = Cartesian3::new(1.0, 0.0, 0.0).rotate_around(&Cartesian3::new(0.0, 0.0, 2.0), FRAC_PI_2);
+    assert!((r - Cartesian3::new(0.0, 1.0, 0.0)).displacement() < 1e-15);
+    //A third of a turn around the diagonal cycles the axes.
+    let diagonal = Cartesian3::new(1.0, 1.0, 1.0);
+    let r = Cartesian3::new(1.0, 0.0, 0.0).rotate_around(&diagonal, 2.0 * PI / 3.0);
+    assert!((r - Cartesian3::new(0.0, 1.0, 0.0)).displacement() < 1e-14);
+    let v = Cartesian3::new(0.3, -2.0, 1.5);
+    assert_eq!(v.rotate_around(&Cartesian3::zero(), 1.0).x, v.x);
+}
@@ -0,0 +1,204 @@
+extern crate num;
+use super::{Vector, Scalar, Cartesian3};
+use self::num::{Float, Zero, FromPrimitive};
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::iter::Sum;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+use std::f64::consts::PI;
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cartesian4<D> {
+    pub x: D,
+    pub y: D,
+    pub z: D,
+    pub w: D,
+}
+
+impl<D> Cartesian4<D> where D: Copy {
+    pub fn new(x: D, y: D, z: D, w: D) -> Self {
+        Cartesian4{x: x, y: y, z: z, w: w}
+    }
+
+    ///Drop the w component to get a Cartesian3.
+    pub fn truncate(&self) -> Cartesian3<D> {
+        Cartesian3{x: self.x, y: self.y, z: self.z}
+    }
+}
+
+///Formats as (x, y, z, w), where the precision and other flags apply to every component
+impl<D> fmt::Display for Cartesian4<D>
+    where D: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.z, f)?;
+        write!(f, ", ")?;
+        fmt::Display::fmt(&self.w, f)?;
+        write!(f, ")")
+    }
+}
+
+///Embed a Cartesian3 into Cartesian4 with the w component set to zero.
+impl<D> From<Cartesian3<D>> for Cartesian4<D>
+    where D: Scalar
+{
+    fn from(v: Cartesian3<D>) -> Self {
+        Cartesian4{x: v.x, y: v.y, z: v.z, w: D::zero()}
+    }
+}
+
+impl<D> Zero for Cartesian4<D>
+    where D: Scalar
+{
+    fn zero() -> Self {
+        Cartesian4{x: D::zero(), y: D::zero(), z: D::zero(), w: D::zero()}
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.y.is_zero() && self.z.is_zero() && self.w.is_zero()
+    }
+}
+
+impl<D> Add for Cartesian4<D>
+    where D: Scalar
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Cartesian4{x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z, w: self.w + rhs.w}
+    }
+}
+
+impl<D> Sum for Cartesian4<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=Self>
+    {
+        iter.fold(Self::zero(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, D> Sum<&'a Cartesian4<D>> for Cartesian4<D>
+    where D: Scalar
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=&'a Self>
+    {
+        iter.fold(Self::zero(), |acc, &v| acc + v)
+    }
+}
+
+impl<D> Sub for Cartesian4<D>
+    where D: Scalar
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Cartesian4{x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z, w: self.w - rhs.w}
+    }
+}
+
+impl<D> Mul<D> for Cartesian4<D>
+    where D: Scalar
+{
+    type Output = Self;
+    fn mul(self, rhs: D) -> Self {
+        Cartesian4{x: self.x * rhs, y: self.y * rhs, z: self.z * rhs, w: self.w * rhs}
+    }
+}
+
+impl<D> Div<D> for Cartesian4<D>
+    where D: Scalar
+{
+    type Output = Self;
+    fn div(self, rhs: D) -> Self {
+        Cartesian4{x: self.x / rhs, y: self.y / rhs, z: self.z / rhs, w: self.w / rhs}
+    }
+}
+
+impl<D> Neg for Cartesian4<D>
+    where D: Scalar
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Cartesian4{x: -self.x, y: -self.y, z: -self.z, w: -self.w}
+    }
+}
+
+impl<D> Vector<D> for Cartesian4<D>
+    where D: Float + FromPrimitive
+{
+    fn space_ball(d: D) -> D {
+        d * d * d * d * D::from_f64(PI * PI / 2.0).unwrap()
+    }
+    fn dot(&lhs: &Self, rhs: &Self) -> D {
+        lhs.x * rhs.x + lhs.y * rhs.y + lhs.z * rhs.z + lhs.w * rhs.w
+    }
+    fn space_box(&self) -> D {
+        self.x * self.y * self.z * self.w
+    }
+    fn displacement(&self) -> D {
+        self.displacement_squared().sqrt()
+    }
+    fn displacement_squared(&self) -> D {
+        self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w
+    }
+    fn dimensions() -> usize {
+        4
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            3 => self.w,
+            _ => panic!("Cartesian4 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 4, "Cartesian4 needs a slice of length 4, but got {}", s.len());
+        Cartesian4{x: s[0], y: s[1], z: s[2], w: s[3]}
+    }
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        Cartesian4{x: f(self.x), y: f(self.y), z: f(self.z), w: f(self.w)}
+    }
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        Cartesian4{x: f(self.x, other.x), y: f(self.y, other.y), z: f(self.z, other.z), w: f(self.w, other.w)}
+    }
+}
+
+#[test]
+fn dot_cartesian4() {
+    let a = Cartesian4::new(1.0, -2.0, 0.5, 3.0);
+    let b = Cartesian4::new(2.0, 1.0, 4.0, -1.0);
+    assert_eq!(Cartesian4::dot(&a, &b), -1.0);
+    assert_eq!(Cartesian4::new(1.0, 1.0, 1.0, 1.0).displacement(), 2.0);
+    assert_eq!(a.displacement_squared(), 14.25);
+    //The unit 4-ball has volume pi^2/2.
+    assert!((Cartesian4::<f64>::space_ball(1.0) - super::VectorN::<f64, 4>::space_ball(1.0)).abs() < 1e-12);
+}
+
+#[test]
+fn gravitate_cartesian4() {
+    use super::super::particle::*;
+    type P = BasicParticle<Cartesian4<f64>, f64>;
+    let mut a = P::new(1.0, Cartesian4::new(-1.0, 0.5, 0.0, 1.0), Cartesian4::zero(), 1.0);
+    let mut b = P::new(1.0, Cartesian4::new(2.0, -1.5, 0.0, -1.0), Cartesian4::zero(), 1.0);
+    gravitate(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    //Equal inertia means the accelerations must be exactly opposite.
+    assert!((a.velocity + b.velocity).is_zero());
+    //Both particles must be pulled toward each other.
+    assert!(a.velocity.x > 0.0 && a.velocity.y < 0.0 && a.velocity.z == 0.0 && a.velocity.w < 0.0);
+}
@@ -3,11 +3,19 @@
 pub mod cartesian1;
 pub mod cartesian2;
 pub mod cartesian3;
+pub mod cartesian4;
+pub mod scalar;
 pub mod space;
+pub mod spherical;
+pub mod vectorn;
 pub use self::cartesian1::*;
 pub use self::cartesian2::*;
 pub use self::cartesian3::*;
+pub use self::cartesian4::*;
+pub use self::scalar::*;
 pub use self::space::*;
+pub use self::spherical::*;
+pub use self::vectorn::*;
 
 extern crate num;
 extern crate nalgebra as na;
@@ -32,20 +40,149 @@ pub trait Vector<D>: Sized + Clone + Copy + Zero + Add<Self, Output=Self> + Sub<
     ///Returns the length of a vector
     fn displacement(&self) -> D;
 
-    ///Returns the squared length of a vector; this is more efficient than displacement() for cartesian vectors
+    ///Returns the amount of components in this type of vector
+    fn dimensions() -> usize;
+
+    ///Returns component i of the vector; this panics if the vector doesn't have that many dimensions
+    ///
+    ///Component i is always the coordinate along Cartesian axis i, even for vectors stored in other coordinates like
+    ///Spherical, since generic code like bounding_box, max_component, and SpatialHash treats them as axes.
+    fn get(&self, i: usize) -> D;
+
+    ///Creates a vector from its components along each Cartesian axis; this panics if the slice length isn't the
+    ///amount of dimensions
+    fn from_slice(s: &[D]) -> Self;
+
+    ///Creates a vector by applying f to every component, such as v.map(|c| c.abs())
+    ///
+    ///This defaults to collecting the components with get and building the vector with from_slice, which allocates;
+    ///override it to build the vector directly.
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        let components: Vec<D> = (0..Self::dimensions()).map(|i| f(self.get(i))).collect();
+        Self::from_slice(&components)
+    }
+
+    ///Creates a vector by applying f to every pair of matching components of this vector and other
+    ///
+    ///This has the same default as map.
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        let components: Vec<D> = (0..Self::dimensions()).map(|i| f(self.get(i), other.get(i))).collect();
+        Self::from_slice(&components)
+    }
+
+    ///Returns the squared length of a vector without taking a square root, so it is cheaper than displacement()
+    ///
+    ///This defaults to the dot product of the vector with itself; override it when there is a faster way.
     fn displacement_squared(&self) -> D {
-        self.displacement().powi(2)
+        Self::dot(self, self)
     }
 
     ///Returns a vector in the same direction as this one, but with length 1
+    ///
+    ///A vector with no displacement has no direction, so the zero vector is returned rather than NaN.
     fn normalized(&self) -> Self {
-        *self / self.displacement()
+        let displacement = self.displacement();
+        if displacement.is_zero() {
+            Self::zero()
+        } else {
+            *self / displacement
+        }
     }
 
     ///Make this vector normalized().
     fn normalize(&mut self) {
         *self = self.normalized();
     }
+
+    ///Reflect this vector off of a surface with the given normal, which must have a length of 1
+    ///
+    ///The component along the normal is flipped while the tangential component is preserved.
+    fn reflect(&self, normal: &Self) -> Self {
+        debug_assert!((normal.displacement_squared() - D::one()).abs() < D::epsilon().sqrt(),
+            "reflect requires a normal with a length of 1");
+        *self - *normal * ((D::one() + D::one()) * Self::dot(self, normal))
+    }
+
+    ///Returns the distance between a and b, which is the displacement of the vector from one to the other
+    fn distance(a: &Self, b: &Self) -> D {
+        (*b - *a).displacement()
+    }
+
+    ///Returns the squared distance between a and b without taking a square root
+    fn distance_squared(a: &Self, b: &Self) -> D {
+        (*b - *a).displacement_squared()
+    }
+
+    ///Returns the component of this vector in the direction of other, or zero if other is zero
+    fn project_onto(&self, other: &Self) -> Self {
+        let other_squared = Self::dot(other, other);
+        if other_squared.is_zero() {
+            Self::zero()
+        } else {
+            *other * (Self::dot(self, other) / other_squared)
+        }
+    }
+
+    ///Returns the component of this vector perpendicular to other, which is what remains after project_onto
+    fn reject_from(&self, other: &Self) -> Self {
+        *self - self.project_onto(other)
+    }
+
+    ///Linearly interpolate from this vector at t = 0 to other at t = 1
+    ///
+    ///The parameter t is not clamped, so values outside of [0, 1] extrapolate along the line through both vectors.
+    fn lerp(&self, other: &Self, t: D) -> Self {
+        *self * (D::one() - t) + *other * t
+    }
+
+    ///Returns the angle between this vector and other in radians, which is zero if either vector is zero
+    fn angle_between(&self, other: &Self) -> D {
+        let lengths = self.displacement() * other.displacement();
+        if lengths.is_zero() {
+            D::zero()
+        } else {
+            //Rounding can push the cosine slightly outside of [-1, 1], where acos is NaN.
+            (Self::dot(self, other) / lengths).max(-D::one()).min(D::one()).acos()
+        }
+    }
+
+    ///Check if every component of this vector is within epsilon of the matching component of other
+    fn approx_eq(&self, other: &Self, epsilon: D) -> bool {
+        (0..Self::dimensions()).all(|i| (self.get(i) - other.get(i)).abs() <= epsilon)
+    }
+
+    ///Returns the largest component of the vector
+    fn max_component(&self) -> D {
+        (1..Self::dimensions()).fold(self.get(0), |max, i| max.max(self.get(i)))
+    }
+
+    ///Returns the smallest component of the vector
+    fn min_component(&self) -> D {
+        (1..Self::dimensions()).fold(self.get(0), |min, i| min.min(self.get(i)))
+    }
+
+    ///Returns the vector with the absolute value of every component
+    fn abs(&self) -> Self {
+        self.map(|c| c.abs())
+    }
+
+    ///Returns true only if every component is finite, which is neither NaN nor infinite
+    fn is_finite(&self) -> bool {
+        (0..Self::dimensions()).all(|i| self.get(i).is_finite())
+    }
+}
+
+#[test]
+fn perp_dot_vector() {
+    let a = Cartesian2::new(2.0, 1.0);
+    assert_eq!(Cartesian2::perp_dot(&a, &Cartesian2::new(-1.0, 2.0)), 5.0);
+    assert_eq!(Cartesian2::perp_dot(&Cartesian2::new(-1.0, 2.0), &a), -5.0);
+    assert_eq!(Cartesian2::perp_dot(&a, &(a * -3.0)), 0.0);
+    assert_eq!(na::Vec2::perp_dot(&na::Vec2{x: 1.0, y: 0.0}, &na::Vec2{x: 0.0, y: 1.0}), 1.0);
 }
 
 #[test]
@@ -54,11 +191,217 @@ fn dot_vector() {
     let _b = Cartesian2::dot(&a, &Cartesian2::new(1.0, 0.5));
 }
 
+#[test]
+fn displacement_squared_vector() {
+    fn check<V: Vector<f64>>(v: V) {
+        assert!((v.displacement_squared() - v.displacement().powi(2)).abs() < 1e-12);
+    }
+    check(Cartesian1::new(-1.5));
+    check(Cartesian2::new(0.3, -4.0));
+    check(Cartesian3::new(0.3, -4.0, 1.2));
+    check(VectorN::new([0.3, -4.0, 1.2, 2.5]));
+    check(na::Vec3{x: 0.3, y: -4.0, z: 1.2});
+    //Squaring the square root of 2 rounds to slightly above 2, so an exact result shows no sqrt was taken.
+    assert_eq!(Cartesian2::new(1.0, 1.0).displacement_squared(), 2.0);
+    assert_eq!(Cartesian3::new(1.0, 1.0, 0.0).displacement_squared(), 2.0);
+    assert_eq!(VectorN::new([1.0, 1.0]).displacement_squared(), 2.0);
+    assert_eq!(na::Vec2{x: 1.0, y: 1.0}.displacement_squared(), 2.0);
+}
+
+#[test]
+fn distance_vector() {
+    let (a, b) = (Cartesian3::new(1.0, -2.0, 0.5), Cartesian3::new(-3.0, 1.0, 0.5));
+    assert_eq!(Cartesian3::distance(&a, &b), 5.0);
+    assert_eq!(Cartesian3::distance(&a, &b), Cartesian3::distance(&b, &a));
+    assert_eq!(Cartesian3::distance(&a, &b), (b - a).displacement());
+    assert_eq!(Cartesian3::distance_squared(&a, &b), 25.0);
+    assert_eq!(Cartesian3::distance_squared(&b, &a), (a - b).displacement_squared());
+    assert_eq!(Cartesian2::distance(&Cartesian2::new(0.5, 0.5), &Cartesian2::new(0.5, 0.5)), 0.0);
+}
+
+#[test]
+fn approx_eq_vector() {
+    let a = Cartesian3::new(1.0, -2.0, 3.0);
+    assert!(a.approx_eq(&Cartesian3::new(1.0 + 1e-10, -2.0, 3.0 - 1e-10), 1e-9));
+    assert!(!a.approx_eq(&Cartesian3::new(1.0, -2.0 + 1e-8, 3.0), 1e-9));
+    assert!(a.approx_eq(&a, 0.0));
+    assert!(!Cartesian2::new(0.1 + 0.2, 0.0).approx_eq(&Cartesian2::new(0.3, 0.0), 0.0));
+    assert!(Cartesian2::new(0.1 + 0.2, 0.0).approx_eq(&Cartesian2::new(0.3, 0.0), 1e-15));
+}
+
+#[test]
+fn normalize_vector() {
+    let mut a = Cartesian3::new(0.3, -4.0, 1.2);
+    a.normalize();
+    assert!((a.displacement() - 1.0).abs() < 1e-12);
+    assert!(Cartesian3::<f64>::zero().normalized().is_zero());
+    assert!(Cartesian2::<f64>::zero().normalized().is_zero());
+}
+
 ///CrossVector is a Vector that has dimensions such that the cross product can be computed.
 pub trait CrossVector {
     fn cross(lhs: &Self, rhs: &Self) -> Self;
 }
 
+///PerpCross is a 2d Vector with a scalar cross product, which is the z component of the cross product if both vectors
+///were in the xy plane of 3d space.
+///
+///The cross product of two 2d vectors points out of the plane, so it can't be another 2d vector as CrossVector
+///requires. Its magnitude is the signed area of the parallelogram spanned by the vectors.
+pub trait PerpCross<D> {
+    fn perp_dot(lhs: &Self, rhs: &Self) -> D;
+}
+
+#[test]
+fn components_vector() {
+    fn round_trip<V: Vector<f64>>() {
+        let components: Vec<f64> = (0..V::dimensions()).map(|i| i as f64 - 0.5).collect();
+        let v = V::from_slice(&components);
+        for (i, &c) in components.iter().enumerate() {
+            assert_eq!(v.get(i), c);
+        }
+    }
+    round_trip::<Cartesian1<f64>>();
+    round_trip::<Cartesian2<f64>>();
+    round_trip::<Cartesian3<f64>>();
+    round_trip::<Cartesian4<f64>>();
+    round_trip::<VectorN<f64, 6>>();
+    round_trip::<na::Vec1<f64>>();
+    round_trip::<na::Vec2<f64>>();
+    round_trip::<na::Vec3<f64>>();
+    assert_eq!(Cartesian3::<f64>::dimensions(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Cartesian2 has no component 2")]
+fn component_out_of_range() {
+    Cartesian2::new(1.0, 2.0).get(2);
+}
+
+#[test]
+fn map_vector() {
+    let a = Cartesian3::new(1.0, -2.0, 3.0);
+    let b = Cartesian3::new(-1.0, 4.0, 2.5);
+    let min = a.zip_map(&b, |l, r| l.min(r));
+    assert_eq!((min.x, min.y, min.z), (-1.0, -2.0, 2.5));
+    let abs = a.map(|c| c.abs());
+    assert_eq!((abs.x, abs.y, abs.z), (1.0, 2.0, 3.0));
+    let clamped = VectorN::new([-3.0, 0.5, 7.0]).map(|c| c.clamp(-1.0, 1.0));
+    assert_eq!(clamped.0, [-1.0, 0.5, 1.0]);
+    let max = VectorN::new([1.0, 5.0]).zip_map(&VectorN::new([2.0, 3.0]), |l, r| l.max(r));
+    assert_eq!(max.0, [2.0, 5.0]);
+    //The nalgebra vectors use the default bodies built on get and from_slice.
+    let (l, r) = (na::Vec2{x: 1.0, y: -4.0}, na::Vec2{x: 0.5, y: 2.0});
+    let (abs, sum) = (l.map(|c| c.abs()), l.zip_map(&r, |a, b| a + b));
+    assert_eq!((abs.x, abs.y, sum.x, sum.y), (1.0, 4.0, 1.5, -2.0));
+}
+
+#[test]
+fn angle_vector() {
+    use std::f64::consts::{PI, FRAC_PI_2};
+    let a = Cartesian3::new(1.0, 2.0, -0.5);
+    assert!((Cartesian2::new(2.0, 0.0).angle_between(&Cartesian2::new(0.0, -3.0)) - FRAC_PI_2).abs() < 1e-12);
+    //The clamp keeps this finite, but acos is steep near 1 so rounding still shows up around the square root of epsilon.
+    assert!(a.angle_between(&(a * 3.0)) < 1e-7);
+    assert!((a.angle_between(&-a) - PI).abs() < 1e-12);
+    assert_eq!(a.angle_between(&Cartesian3::zero()), 0.0);
+    let b = Cartesian3::new(0.1, 0.7, 0.3);
+    assert!(b.angle_between(&b) < 1e-7 && !(b * -0.5).angle_between(&b).is_nan());
+}
+
+#[test]
+fn component_reductions_vector() {
+    let v = Cartesian4::new(-3.0, 2.5, 0.0, -0.5);
+    assert_eq!((v.max_component(), v.min_component()), (2.5, -3.0));
+    let a = v.abs();
+    assert_eq!((a.x, a.y, a.z, a.w), (3.0, 2.5, 0.0, 0.5));
+    assert_eq!((a.max_component(), a.min_component()), (3.0, 0.0));
+    assert_eq!(Cartesian1::new(-2.0).max_component(), -2.0);
+    let n = VectorN::new([1.0, -7.0, 4.0]);
+    assert_eq!((n.max_component(), n.min_component(), n.abs().max_component()), (4.0, -7.0, 7.0));
+}
+
+#[test]
+fn is_finite_vector() {
+    assert!(Cartesian3::new(1.0, -2.0, 1e300).is_finite());
+    assert!(!Cartesian3::new(1.0, f64::INFINITY, 0.0).is_finite());
+    assert!(!Cartesian2::new(f64::NAN, 0.0).is_finite());
+    assert!(!VectorN::new([0.0, 0.0, -f64::INFINITY]).is_finite());
+}
+
+#[test]
+fn sum_vector() {
+    use super::particle::*;
+    let particles = [
+        BasicParticle::new(1.0, Cartesian1::new(0.0), Cartesian1::new(2.0), 1.5),
+        BasicParticle::new(1.0, Cartesian1::new(1.0), Cartesian1::new(-0.5), 2.0),
+        BasicParticle::new(1.0, Cartesian1::new(2.0), Cartesian1::new(0.25), 4.0),
+    ];
+    let summed = particles.iter().map(|p| p.velocity * p.inertia).sum::<Cartesian1<f64>>();
+    let folded = particles.iter().fold(Cartesian1::zero(), |acc, p| acc + p.velocity * p.inertia);
+    assert_eq!(summed.x, folded.x);
+    assert_eq!(summed.x, 3.0);
+    let vs = [Cartesian3::new(1.0, 2.0, 3.0), Cartesian3::new(-1.0, 0.5, 0.0)];
+    let total: Cartesian3<f64> = vs.iter().sum();
+    assert_eq!((total.x, total.y, total.z), (0.0, 2.5, 3.0));
+    assert!(Vec::<Cartesian2<f64>>::new().into_iter().sum::<Cartesian2<f64>>().is_zero());
+}
+
+#[test]
+fn display_vector() {
+    assert_eq!(format!("{}", Cartesian1::new(0.5)), "(0.5)");
+    assert_eq!(format!("{:.3}", Cartesian2::new(1.0, 2.0 / 3.0)), "(1.000, 0.667)");
+    assert_eq!(format!("{}", Cartesian3::new(1.0, -2.0, 3.5)), "(1, -2, 3.5)");
+    assert_eq!(format!("{:.1}", Cartesian4::new(1.0, 2.0, 3.0, 4.0)), "(1.0, 2.0, 3.0, 4.0)");
+    assert_eq!(format!("{:.1}", VectorN::new([0.25, 1.0, -1.0])), "(0.2, 1.0, -1.0)");
+}
+
+#[test]
+fn embed_vector() {
+    let a = Cartesian1::new(1.5);
+    let b = Cartesian2::from(a);
+    assert_eq!((b.x, b.y), (1.5, 0.0));
+    let c: Cartesian3<f64> = Cartesian2::new(1.5, -2.0).into();
+    assert_eq!((c.x, c.y, c.z), (1.5, -2.0, 0.0));
+    let d = Cartesian4::from(Cartesian3::new(1.5, -2.0, 3.0));
+    assert_eq!((d.x, d.y, d.z, d.w), (1.5, -2.0, 3.0, 0.0));
+    //Truncating undoes embedding.
+    assert_eq!(b.truncate().x, a.x);
+    assert_eq!((c.truncate().x, c.truncate().y), (1.5, -2.0));
+    let back = Cartesian4::from(Cartesian3::from(Cartesian2::from(a))).truncate().truncate().truncate();
+    assert_eq!(back.x, a.x);
+    assert!(Cartesian4::new(1.0, 2.0, 3.0, 4.0).truncate().displacement_squared() == 14.0);
+}
+
+#[test]
+fn lerp_vector() {
+    let a = Cartesian2::new(1.0, -2.0);
+    let b = Cartesian2::new(3.0, 4.0);
+    assert!((a.lerp(&b, 0.0) - a).is_zero());
+    assert!((a.lerp(&b, 1.0) - b).is_zero());
+    assert!((a.lerp(&b, 0.5) - Cartesian2::new(2.0, 1.0)).is_zero());
+    assert!((a.lerp(&b, 2.0) - Cartesian2::new(5.0, 10.0)).is_zero());
+}
+
+#[test]
+fn reflect_vector() {
+    let v = Cartesian2::new(3.0, -2.0);
+    let r = v.reflect(&Cartesian2::new(0.0, 1.0));
+    assert_eq!((r.x, r.y), (3.0, 2.0));
+    let r = Cartesian3::new(1.0, 1.0, 0.0).reflect(&Cartesian3::new(-1.0, 0.0, 0.0));
+    assert_eq!((r.x, r.y, r.z), (-1.0, 1.0, 0.0));
+}
+
+#[test]
+fn project_vector() {
+    let v = Cartesian3::new(2.0, -1.0, 3.0);
+    let onto = Cartesian3::new(0.5, 1.5, 1.0);
+    let (projection, rejection) = (v.project_onto(&onto), v.reject_from(&onto));
+    assert!((projection + rejection - v).displacement() < 1e-12);
+    assert!(Cartesian3::dot(&rejection, &onto).abs() < 1e-12);
+    assert!(v.project_onto(&Cartesian3::zero()).is_zero());
+}
+
 #[test]
 fn cross_vector() {
     let a = Cartesian3::new(0.3, 0.5, 1.0);
@@ -80,6 +423,22 @@ impl<D> Vector<D> for na::Vec1<D>
     fn displacement(&self) -> D {
         self.x
     }
+    fn displacement_squared(&self) -> D {
+        self.x * self.x
+    }
+    fn dimensions() -> usize {
+        1
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            _ => panic!("na::Vec1 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 1, "na::Vec1 needs a slice of length 1, but got {}", s.len());
+        na::Vec1{x: s[0]}
+    }
 }
 
 impl<D> Vector<D> for na::Vec2<D>
@@ -100,6 +459,20 @@ impl<D> Vector<D> for na::Vec2<D>
     fn displacement_squared(&self) -> D {
         self.x * self.x + self.y * self.y
     }
+    fn dimensions() -> usize {
+        2
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("na::Vec2 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 2, "na::Vec2 needs a slice of length 2, but got {}", s.len());
+        na::Vec2{x: s[0], y: s[1]}
+    }
 }
 
 impl<D> Vector<D> for na::Vec3<D>
@@ -120,6 +493,29 @@ impl<D> Vector<D> for na::Vec3<D>
     fn displacement_squared(&self) -> D {
         self.x * self.x + self.y * self.y + self.z * self.z
     }
+    fn dimensions() -> usize {
+        3
+    }
+    fn get(&self, i: usize) -> D {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("na::Vec3 has no component {}", i),
+        }
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 3, "na::Vec3 needs a slice of length 3, but got {}", s.len());
+        na::Vec3{x: s[0], y: s[1], z: s[2]}
+    }
+}
+
+impl<D> PerpCross<D> for na::Vec2<D>
+    where D: Float
+{
+    fn perp_dot(lhs: &Self, rhs: &Self) -> D {
+        lhs.x * rhs.y - lhs.y * rhs.x
+    }
 }
 
 impl<D> CrossVector for na::Vec3<D>
@@ -0,0 +1,34 @@
+extern crate num;
+use self::num::Num;
+use std::ops::Neg;
+
+///The arithmetic the Cartesian vectors need from their components, which is less than Float
+///
+///Only the operators of the vectors are implemented for any Scalar. Vector and the particle functions still require
+///Float, since displacement, normalization, and the force falloffs need a real square root and ordering. This lets
+///a Cartesian1<Complex<f64>> hold complex amplitudes and be added, subtracted, and scaled like any other vector.
+///
+///Every type with the arithmetic is a Scalar, which includes every Float. The impl can't be written over Float alone,
+///since Complex isn't Float and Rust won't allow a second impl for it next to a blanket impl over a foreign trait.
+pub trait Scalar: Copy + Num + Neg<Output=Self> {}
+
+impl<T> Scalar for T
+    where T: Copy + Num + Neg<Output=T>
+{
+}
+
+#[test]
+fn complex_vector_test() {
+    use self::num::complex::Complex;
+    use self::num::Zero;
+    use super::{Cartesian1, Cartesian2};
+    let a = Cartesian1::new(Complex::new(1.0, 2.0));
+    let b = Cartesian1::new(Complex::new(-0.5, 0.5));
+    let sum = a + b;
+    assert_eq!((sum.x.re, sum.x.im), (0.5, 2.5));
+    //Scaling by i rotates the amplitude by a quarter turn.
+    let turned = a * Complex::i() - b;
+    assert_eq!((turned.x.re, turned.x.im), (-1.5, 0.5));
+    let c = Cartesian2::new(Complex::new(0.0, 1.0), Complex::new(2.0, 0.0));
+    assert!((c + -c).is_zero());
+}
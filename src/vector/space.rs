@@ -12,6 +12,15 @@ pub trait Toroid<V> {
     fn wrap_position(&self, pos: V) -> V;
 }
 
+/// Anything that implements this trait has walls which keep positions inside of the space.
+pub trait Walled<V, D> {
+    /// Clamp a position to the walls and reflect its velocity off of each wall it crossed
+    ///
+    /// The velocity component normal to a crossed wall is negated and scaled by restitution, but only if it is still
+    /// moving out through the wall.
+    fn reflect(&self, pos: V, vel: V, restitution: D) -> (V, V);
+}
+
 pub trait Ball<D> {
     fn radius(&self) -> D;
 
@@ -24,7 +33,9 @@ pub trait Ball<D> {
 
 /// A Box with a center at origin and one of the corners created by offset
 ///
-/// The box is aligned so that the face normals point along each axis.
+/// The box is aligned so that the face normals point along each axis. As a Toroid, wrap_delta gives the delta to the
+/// nearest periodic image, so passing it as the comp_delta closure of gravitate_delta and friends applies the minimum
+/// image convention.
 pub struct Box<V> {
     pub origin: V,
     pub offset: V,
@@ -38,6 +49,17 @@ impl<V> Box<V> {
         }
     }
 
+    /// Create a box from its minimum and maximum corners
+    pub fn from_corners<D>(min: V, max: V) -> Self
+        where V: Vector<D>, D: Float
+    {
+        let two = D::one() + D::one();
+        Box{
+            origin: (min + max) / two,
+            offset: (max - min) / two,
+        }
+    }
+
     //Compute the amount of space contained in the box
     pub fn space<D>(&self) -> D
         where V: Vector<D>, D: Float
@@ -62,6 +84,23 @@ fn wrap_scalar<D>(pos: D, bound: D) -> D where D: Float + FromPrimitive {
     }
 }
 
+fn reflect_scalar<D>(pos: D, vel: D, origin: D, bound: D, restitution: D) -> (D, D) where D: Float {
+    reflect_scalar_walls(pos, vel, origin, bound, restitution, restitution)
+}
+
+// Same as reflect_scalar, but the lower and upper walls each have their own restitution
+fn reflect_scalar_walls<D>(pos: D, vel: D, origin: D, bound: D, lower: D, upper: D) -> (D, D) where D: Float {
+    // Bound must be positive
+    let bound = bound.abs();
+    if pos > origin + bound {
+        (origin + bound, if vel > D::zero() {-vel * upper} else {vel})
+    } else if pos < origin - bound {
+        (origin - bound, if vel < D::zero() {-vel * lower} else {vel})
+    } else {
+        (pos, vel)
+    }
+}
+
 impl<D> Toroid<Cartesian1<D>> for Box<Cartesian1<D>>
     where D: Float + FromPrimitive
 {
@@ -152,6 +191,162 @@ impl<D> Toroid<na::Vec3<D>> for Box<na::Vec3<D>>
     }
 }
 
+impl<D> Walled<Cartesian1<D>, D> for Box<Cartesian1<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: Cartesian1<D>, vel: Cartesian1<D>, restitution: D) -> (Cartesian1<D>, Cartesian1<D>) {
+        let (x, vx) = reflect_scalar(pos.x, vel.x, self.origin.x, self.offset.x, restitution);
+        (Cartesian1{x: x}, Cartesian1{x: vx})
+    }
+}
+
+impl<D> Walled<Cartesian2<D>, D> for Box<Cartesian2<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: Cartesian2<D>, vel: Cartesian2<D>, restitution: D) -> (Cartesian2<D>, Cartesian2<D>) {
+        let (x, vx) = reflect_scalar(pos.x, vel.x, self.origin.x, self.offset.x, restitution);
+        let (y, vy) = reflect_scalar(pos.y, vel.y, self.origin.y, self.offset.y, restitution);
+        (Cartesian2{x: x, y: y}, Cartesian2{x: vx, y: vy})
+    }
+}
+
+impl<D> Walled<Cartesian3<D>, D> for Box<Cartesian3<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: Cartesian3<D>, vel: Cartesian3<D>, restitution: D) -> (Cartesian3<D>, Cartesian3<D>) {
+        let (x, vx) = reflect_scalar(pos.x, vel.x, self.origin.x, self.offset.x, restitution);
+        let (y, vy) = reflect_scalar(pos.y, vel.y, self.origin.y, self.offset.y, restitution);
+        let (z, vz) = reflect_scalar(pos.z, vel.z, self.origin.z, self.offset.z, restitution);
+        (Cartesian3{x: x, y: y, z: z}, Cartesian3{x: vx, y: vy, z: vz})
+    }
+}
+
+impl<D> Walled<na::Vec1<D>, D> for Box<na::Vec1<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: na::Vec1<D>, vel: na::Vec1<D>, restitution: D) -> (na::Vec1<D>, na::Vec1<D>) {
+        let (x, vx) = reflect_scalar(pos.x, vel.x, self.origin.x, self.offset.x, restitution);
+        (na::Vec1{x: x}, na::Vec1{x: vx})
+    }
+}
+
+impl<D> Walled<na::Vec2<D>, D> for Box<na::Vec2<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: na::Vec2<D>, vel: na::Vec2<D>, restitution: D) -> (na::Vec2<D>, na::Vec2<D>) {
+        let (x, vx) = reflect_scalar(pos.x, vel.x, self.origin.x, self.offset.x, restitution);
+        let (y, vy) = reflect_scalar(pos.y, vel.y, self.origin.y, self.offset.y, restitution);
+        (na::Vec2{x: x, y: y}, na::Vec2{x: vx, y: vy})
+    }
+}
+
+impl<D> Walled<na::Vec3<D>, D> for Box<na::Vec3<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: na::Vec3<D>, vel: na::Vec3<D>, restitution: D) -> (na::Vec3<D>, na::Vec3<D>) {
+        let (x, vx) = reflect_scalar(pos.x, vel.x, self.origin.x, self.offset.x, restitution);
+        let (y, vy) = reflect_scalar(pos.y, vel.y, self.origin.y, self.offset.y, restitution);
+        let (z, vz) = reflect_scalar(pos.z, vel.z, self.origin.z, self.offset.z, restitution);
+        (na::Vec3{x: x, y: y, z: z}, na::Vec3{x: vx, y: vy, z: vz})
+    }
+}
+
+/// A Box where every wall has its own coefficient of restitution, like a sticky floor with bouncy walls
+///
+/// Component i of lower is the restitution of the wall at the minimum of axis i and component i of upper is the
+/// restitution of the wall at its maximum. As a Walled space, the restitution passed to reflect scales the coefficient
+/// of the crossed wall, so passing one uses the coefficients as they are.
+pub struct RestitutionBox<V> {
+    pub space: Box<V>,
+    pub lower: V,
+    pub upper: V,
+}
+
+impl<V> RestitutionBox<V> {
+    /// Create a box from its minimum and maximum corners and the restitutions of its lower and upper walls
+    pub fn with_restitutions<D>(min: V, max: V, coeffs: (V, V)) -> Self
+        where V: Vector<D>, D: Float
+    {
+        RestitutionBox{
+            space: Box::from_corners(min, max),
+            lower: coeffs.0,
+            upper: coeffs.1,
+        }
+    }
+}
+
+impl<D> Walled<Cartesian1<D>, D> for RestitutionBox<Cartesian1<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: Cartesian1<D>, vel: Cartesian1<D>, restitution: D) -> (Cartesian1<D>, Cartesian1<D>) {
+        let (x, vx) = reflect_scalar_walls(pos.x, vel.x, self.space.origin.x, self.space.offset.x,
+            self.lower.x * restitution, self.upper.x * restitution);
+        (Cartesian1{x: x}, Cartesian1{x: vx})
+    }
+}
+
+impl<D> Walled<Cartesian2<D>, D> for RestitutionBox<Cartesian2<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: Cartesian2<D>, vel: Cartesian2<D>, restitution: D) -> (Cartesian2<D>, Cartesian2<D>) {
+        let (x, vx) = reflect_scalar_walls(pos.x, vel.x, self.space.origin.x, self.space.offset.x,
+            self.lower.x * restitution, self.upper.x * restitution);
+        let (y, vy) = reflect_scalar_walls(pos.y, vel.y, self.space.origin.y, self.space.offset.y,
+            self.lower.y * restitution, self.upper.y * restitution);
+        (Cartesian2{x: x, y: y}, Cartesian2{x: vx, y: vy})
+    }
+}
+
+impl<D> Walled<Cartesian3<D>, D> for RestitutionBox<Cartesian3<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: Cartesian3<D>, vel: Cartesian3<D>, restitution: D) -> (Cartesian3<D>, Cartesian3<D>) {
+        let (x, vx) = reflect_scalar_walls(pos.x, vel.x, self.space.origin.x, self.space.offset.x,
+            self.lower.x * restitution, self.upper.x * restitution);
+        let (y, vy) = reflect_scalar_walls(pos.y, vel.y, self.space.origin.y, self.space.offset.y,
+            self.lower.y * restitution, self.upper.y * restitution);
+        let (z, vz) = reflect_scalar_walls(pos.z, vel.z, self.space.origin.z, self.space.offset.z,
+            self.lower.z * restitution, self.upper.z * restitution);
+        (Cartesian3{x: x, y: y, z: z}, Cartesian3{x: vx, y: vy, z: vz})
+    }
+}
+
+impl<D> Walled<na::Vec1<D>, D> for RestitutionBox<na::Vec1<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: na::Vec1<D>, vel: na::Vec1<D>, restitution: D) -> (na::Vec1<D>, na::Vec1<D>) {
+        let (x, vx) = reflect_scalar_walls(pos.x, vel.x, self.space.origin.x, self.space.offset.x,
+            self.lower.x * restitution, self.upper.x * restitution);
+        (na::Vec1{x: x}, na::Vec1{x: vx})
+    }
+}
+
+impl<D> Walled<na::Vec2<D>, D> for RestitutionBox<na::Vec2<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: na::Vec2<D>, vel: na::Vec2<D>, restitution: D) -> (na::Vec2<D>, na::Vec2<D>) {
+        let (x, vx) = reflect_scalar_walls(pos.x, vel.x, self.space.origin.x, self.space.offset.x,
+            self.lower.x * restitution, self.upper.x * restitution);
+        let (y, vy) = reflect_scalar_walls(pos.y, vel.y, self.space.origin.y, self.space.offset.y,
+            self.lower.y * restitution, self.upper.y * restitution);
+        (na::Vec2{x: x, y: y}, na::Vec2{x: vx, y: vy})
+    }
+}
+
+impl<D> Walled<na::Vec3<D>, D> for RestitutionBox<na::Vec3<D>>
+    where D: Float
+{
+    fn reflect(&self, pos: na::Vec3<D>, vel: na::Vec3<D>, restitution: D) -> (na::Vec3<D>, na::Vec3<D>) {
+        let (x, vx) = reflect_scalar_walls(pos.x, vel.x, self.space.origin.x, self.space.offset.x,
+            self.lower.x * restitution, self.upper.x * restitution);
+        let (y, vy) = reflect_scalar_walls(pos.y, vel.y, self.space.origin.y, self.space.offset.y,
+            self.lower.y * restitution, self.upper.y * restitution);
+        let (z, vz) = reflect_scalar_walls(pos.z, vel.z, self.space.origin.z, self.space.offset.z,
+            self.lower.z * restitution, self.upper.z * restitution);
+        (na::Vec3{x: x, y: y, z: z}, na::Vec3{x: vx, y: vy, z: vz})
+    }
+}
+
 impl<V> Clone for Box<V>
     where V: Clone
 {
@@ -162,3 +357,18 @@ impl<V> Clone for Box<V>
         }
     }
 }
+
+#[test]
+fn restitution_box_test() {
+    let space = RestitutionBox::with_restitutions(Cartesian2::new(0.0, 0.0), Cartesian2::new(10.0, 10.0),
+        (Cartesian2::new(0.5, 0.0), Cartesian2::new(0.9, 1.0)));
+    //The floor at y = 0 is sticky.
+    let (pos, vel) = space.reflect(Cartesian2::new(5.0, -1.0), Cartesian2::new(1.0, -4.0), 1.0);
+    assert_eq!((pos.x, pos.y, vel.x, vel.y), (5.0, 0.0, 1.0, 0.0));
+    //The wall at x = 10 bounces back most of the velocity.
+    let (pos, vel) = space.reflect(Cartesian2::new(11.0, 5.0), Cartesian2::new(2.0, 1.0), 1.0);
+    assert_eq!((pos.x, pos.y, vel.x, vel.y), (10.0, 5.0, -1.8, 1.0));
+    //The restitution passed in scales the wall's own coefficient.
+    let (_, vel) = space.reflect(Cartesian2::new(-1.0, 5.0), Cartesian2::new(-2.0, 0.0), 0.5);
+    assert_eq!(vel.x, 0.5);
+}
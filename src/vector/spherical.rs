@@ -0,0 +1,183 @@
+extern crate num;
+use super::{Vector, Cartesian3};
+use self::num::{Float, Zero, FromPrimitive};
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::f64::consts::PI;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+///A 3d vector in spherical coordinates with radius r, polar angle theta from the z axis, and azimuth phi around it
+///
+///Addition is not linear in spherical coordinates, so every arithmetic operation converts to Cartesian3 and back,
+///which costs several trigonometric functions and a square root. Prefer Cartesian3 in hot loops and only use this
+///where spherical coordinates are needed; displacement is the one operation that is cheaper since it is just r.
+///As a Vector, the components from get and from_slice are the x, y, and z axes, so that generic code like
+///bounding boxes and max_component agrees with Cartesian3.
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spherical<D> {
+    pub r: D,
+    pub theta: D,
+    pub phi: D,
+}
+
+impl<D> Spherical<D> where D: Copy {
+    pub fn new(r: D, theta: D, phi: D) -> Self {
+        Spherical{r: r, theta: theta, phi: phi}
+    }
+}
+
+impl<D> Spherical<D> where D: Float {
+    ///Convert a Cartesian3 into spherical coordinates; the zero vector has both angles set to zero
+    pub fn from_cartesian(c: Cartesian3<D>) -> Self {
+        let r = (c.x * c.x + c.y * c.y + c.z * c.z).sqrt();
+        if r.is_zero() {
+            Spherical::zero()
+        } else {
+            Spherical{r: r, theta: (c.z / r).max(-D::one()).min(D::one()).acos(), phi: c.y.atan2(c.x)}
+        }
+    }
+
+    ///Convert into cartesian coordinates
+    pub fn to_cartesian(&self) -> Cartesian3<D> {
+        let (sin_theta, cos_theta) = self.theta.sin_cos();
+        let (sin_phi, cos_phi) = self.phi.sin_cos();
+        Cartesian3{
+            x: self.r * sin_theta * cos_phi,
+            y: self.r * sin_theta * sin_phi,
+            z: self.r * cos_theta,
+        }
+    }
+}
+
+impl<D> Zero for Spherical<D>
+    where D: Float
+{
+    fn zero() -> Self {
+        Spherical{r: D::zero(), theta: D::zero(), phi: D::zero()}
+    }
+
+    fn is_zero(&self) -> bool {
+        self.r.is_zero()
+    }
+}
+
+impl<D> Add for Spherical<D>
+    where D: Float
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Spherical::from_cartesian(self.to_cartesian() + rhs.to_cartesian())
+    }
+}
+
+impl<D> Sub for Spherical<D>
+    where D: Float
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Spherical::from_cartesian(self.to_cartesian() - rhs.to_cartesian())
+    }
+}
+
+impl<D> Mul<D> for Spherical<D>
+    where D: Float
+{
+    type Output = Self;
+    fn mul(self, rhs: D) -> Self {
+        Spherical::from_cartesian(self.to_cartesian() * rhs)
+    }
+}
+
+impl<D> Div<D> for Spherical<D>
+    where D: Float
+{
+    type Output = Self;
+    fn div(self, rhs: D) -> Self {
+        Spherical::from_cartesian(self.to_cartesian() / rhs)
+    }
+}
+
+impl<D> Neg for Spherical<D>
+    where D: Float
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Spherical::from_cartesian(-self.to_cartesian())
+    }
+}
+
+impl<D> Vector<D> for Spherical<D>
+    where D: Float + FromPrimitive
+{
+    fn space_ball(d: D) -> D {
+        d * d * d * D::from_f64(4.0 / 3.0 * PI).unwrap()
+    }
+    fn dot(&lhs: &Self, rhs: &Self) -> D {
+        lhs.r * rhs.r * (lhs.theta.sin() * rhs.theta.sin() * (lhs.phi - rhs.phi).cos() +
+            lhs.theta.cos() * rhs.theta.cos())
+    }
+    fn space_box(&self) -> D {
+        self.to_cartesian().space_box()
+    }
+    fn displacement(&self) -> D {
+        self.r
+    }
+    fn displacement_squared(&self) -> D {
+        self.r * self.r
+    }
+    fn dimensions() -> usize {
+        3
+    }
+    //The components are the Cartesian axes, like every other Vector, and not r, theta, and phi.
+    fn get(&self, i: usize) -> D {
+        assert!(i < 3, "Spherical has no component {}", i);
+        self.to_cartesian().get(i)
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == 3, "Spherical needs a slice of length 3, but got {}", s.len());
+        Spherical::from_cartesian(Cartesian3::from_slice(s))
+    }
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        Spherical::from_cartesian(self.to_cartesian().map(f))
+    }
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        Spherical::from_cartesian(self.to_cartesian().zip_map(&other.to_cartesian(), f))
+    }
+}
+
+#[test]
+fn spherical_gravitate() {
+    use super::super::particle::*;
+    let positions = [Cartesian3::new(0.5, -1.0, 2.0), Cartesian3::new(-1.5, 0.25, 1.0)];
+    let mut a = BasicParticle::new(1.0, positions[0], Cartesian3::zero(), 1.0);
+    let mut b = BasicParticle::new(2.0, positions[1], Cartesian3::zero(), 1.0);
+    let mut sa = BasicParticle::new(1.0, Spherical::from_cartesian(positions[0]), Spherical::zero(), 1.0);
+    let mut sb = BasicParticle::new(2.0, Spherical::from_cartesian(positions[1]), Spherical::zero(), 1.0);
+    gravitate(&a, &b, 1.0);
+    gravitate(&sa, &sb, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    sa.advance(1.0);
+    sb.advance(1.0);
+    assert!((sa.velocity.to_cartesian() - a.velocity).displacement() < 1e-12);
+    assert!((sb.position.to_cartesian() - b.position).displacement() < 1e-12);
+}
+
+#[test]
+fn spherical_components() {
+    let c = Cartesian3::new(-2.0, 0.5, 1.5);
+    let s = Spherical::from_cartesian(c);
+    for i in 0..3 {
+        assert!((s.get(i) - c.get(i)).abs() < 1e-12);
+    }
+    assert!((s.max_component() - 1.5).abs() < 1e-12 && (s.min_component() + 2.0).abs() < 1e-12);
+    assert!((s.abs().to_cartesian() - Cartesian3::new(2.0, 0.5, 1.5)).displacement() < 1e-12);
+    assert!((Spherical::from_slice(&[-2.0, 0.5, 1.5]).to_cartesian() - c).displacement() < 1e-12);
+    let sum = s.zip_map(&Spherical::from_cartesian(Cartesian3::new(1.0, 1.0, 1.0)), |a, b| a + b);
+    assert!((sum.to_cartesian() - Cartesian3::new(-1.0, 1.5, 2.5)).displacement() < 1e-12);
+}
@@ -0,0 +1,218 @@
+extern crate num;
+use super::Vector;
+use self::num::{Float, Zero, FromPrimitive};
+use std::ops::{Add, Sub, Neg, Mul, Div};
+use std::iter::Sum;
+use std::fmt;
+use std::f64::consts::PI;
+
+///A vector with an arbitrary amount of dimensions N backed by an array
+#[derive(Copy, Clone)]
+pub struct VectorN<D, const N: usize>(pub [D; N]);
+
+impl<D, const N: usize> VectorN<D, N> where D: Copy {
+    pub fn new(components: [D; N]) -> Self {
+        VectorN(components)
+    }
+}
+
+///Formats the components in parentheses separated by commas, where the precision and other flags apply to each
+impl<D, const N: usize> fmt::Display for VectorN<D, N>
+    where D: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            fmt::Display::fmt(c, f)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<D, const N: usize> Zero for VectorN<D, N>
+    where D: Float
+{
+    fn zero() -> Self {
+        VectorN([D::zero(); N])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|c| c.is_zero())
+    }
+}
+
+impl<D, const N: usize> Add for VectorN<D, N>
+    where D: Float
+{
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        for (l, r) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *l = *l + *r;
+        }
+        self
+    }
+}
+
+impl<D, const N: usize> Sum for VectorN<D, N>
+    where D: Float
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=Self>
+    {
+        iter.fold(Self::zero(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, D, const N: usize> Sum<&'a VectorN<D, N>> for VectorN<D, N>
+    where D: Float
+{
+    fn sum<I>(iter: I) -> Self
+        where I: Iterator<Item=&'a Self>
+    {
+        iter.fold(Self::zero(), |acc, &v| acc + v)
+    }
+}
+
+impl<D, const N: usize> Sub for VectorN<D, N>
+    where D: Float
+{
+    type Output = Self;
+    fn sub(mut self, rhs: Self) -> Self {
+        for (l, r) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *l = *l - *r;
+        }
+        self
+    }
+}
+
+impl<D, const N: usize> Mul<D> for VectorN<D, N>
+    where D: Float
+{
+    type Output = Self;
+    fn mul(mut self, rhs: D) -> Self {
+        for c in self.0.iter_mut() {
+            *c = *c * rhs;
+        }
+        self
+    }
+}
+
+impl<D, const N: usize> Div<D> for VectorN<D, N>
+    where D: Float
+{
+    type Output = Self;
+    fn div(mut self, rhs: D) -> Self {
+        for c in self.0.iter_mut() {
+            *c = *c / rhs;
+        }
+        self
+    }
+}
+
+impl<D, const N: usize> Neg for VectorN<D, N>
+    where D: Float
+{
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        for c in self.0.iter_mut() {
+            *c = -*c;
+        }
+        self
+    }
+}
+
+impl<D, const N: usize> Vector<D> for VectorN<D, N>
+    where D: Float + FromPrimitive
+{
+    fn space_ball(d: D) -> D {
+        //Walk the recurrence V(n) = V(n - 2) * 2pi / n from V(0) = 1 or V(1) = 2, which avoids the gamma function.
+        let mut unit = if N.is_multiple_of(2) {1.0} else {2.0};
+        let mut n = 2 + N % 2;
+        while n <= N {
+            unit *= 2.0 * PI / n as f64;
+            n += 2;
+        }
+        D::from_f64(unit).unwrap() * d.powi(N as i32)
+    }
+    fn dot(&lhs: &Self, rhs: &Self) -> D {
+        lhs.0.iter().zip(rhs.0.iter()).fold(D::zero(), |acc, (&l, &r)| acc + l * r)
+    }
+    fn space_box(&self) -> D {
+        self.0.iter().fold(D::one(), |acc, &c| acc * c)
+    }
+    fn displacement(&self) -> D {
+        self.displacement_squared().sqrt()
+    }
+    fn displacement_squared(&self) -> D {
+        Self::dot(self, self)
+    }
+    fn dimensions() -> usize {
+        N
+    }
+    fn get(&self, i: usize) -> D {
+        assert!(i < N, "VectorN<{}> has no component {}", N, i);
+        self.0[i]
+    }
+    fn from_slice(s: &[D]) -> Self {
+        assert!(s.len() == N, "VectorN<{}> needs a slice of length {}, but got {}", N, N, s.len());
+        let mut v = VectorN([D::zero(); N]);
+        v.0.copy_from_slice(s);
+        v
+    }
+    fn map<F>(&self, f: F) -> Self
+        where F: Fn(D) -> D
+    {
+        let mut v = *self;
+        for c in v.0.iter_mut() {
+            *c = f(*c);
+        }
+        v
+    }
+    fn zip_map<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(D, D) -> D
+    {
+        let mut v = *self;
+        for (c, &o) in v.0.iter_mut().zip(other.0.iter()) {
+            *c = f(*c, o);
+        }
+        v
+    }
+}
+
+#[test]
+fn vectorn_matches_cartesian1() {
+    use super::Cartesian1;
+    let a = VectorN::new([0.75f64]);
+    let b = VectorN::new([2.5f64]);
+    let (ca, cb) = (Cartesian1::new(0.75f64), Cartesian1::new(2.5f64));
+    assert_eq!(VectorN::dot(&a, &b), Cartesian1::dot(&ca, &cb));
+    assert_eq!(a.displacement(), ca.displacement());
+    assert_eq!(a.space_box(), ca.space_box());
+    assert_eq!(VectorN::<f64, 1>::space_ball(1.5), Cartesian1::<f64>::space_ball(1.5));
+    assert_eq!((a - b * 2.0).0[0], (ca - cb * 2.0).x);
+}
+
+#[test]
+fn vectorn_space_ball() {
+    use super::{Cartesian2, Cartesian3};
+    assert!((VectorN::<f64, 2>::space_ball(1.5) - Cartesian2::<f64>::space_ball(1.5)).abs() < 1e-12);
+    assert!((VectorN::<f64, 3>::space_ball(1.5) - Cartesian3::<f64>::space_ball(1.5)).abs() < 1e-12);
+    //The unit 5-ball has volume 8pi^2/15.
+    assert!((VectorN::<f64, 5>::space_ball(1.0) - 8.0 * PI * PI / 15.0).abs() < 1e-12);
+}
+
+#[test]
+fn vectorn_particle() {
+    use super::super::particle::*;
+    type P = BasicParticle<VectorN<f64, 5>, f64>;
+    let mut a = P::new(1.0, VectorN::new([0.0, 1.0, 0.0, 2.0, 0.0]), VectorN::zero(), 1.0);
+    let mut b = P::new(1.0, VectorN::new([1.0, 0.0, 0.0, 2.0, 0.5]), VectorN::zero(), 1.0);
+    gravitate(&a, &b, 1.0);
+    a.advance(1.0);
+    b.advance(1.0);
+    assert!((a.velocity + b.velocity).is_zero());
+    assert!(a.velocity.0[0] > 0.0 && a.velocity.0[1] < 0.0 && a.velocity.0[3] == 0.0);
+}
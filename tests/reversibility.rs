@@ -0,0 +1,42 @@
+extern crate zoom;
+use zoom::{BasicParticle, Cartesian3, ParticleSystem, Vector};
+
+type P = BasicParticle<Cartesian3<f64>, f64>;
+
+fn system() -> ParticleSystem<P, Cartesian3<f64>, f64> {
+    let mut system = ParticleSystem::new();
+    system.push(P::new(1.0, Cartesian3::new(-0.5, 0.0, 0.0), Cartesian3::new(0.0, -0.6, 0.1), 1.0));
+    system.push(P::new(1.0, Cartesian3::new(0.5, 0.0, 0.0), Cartesian3::new(0.0, 0.6, 0.0), 1.0));
+    system.push(P::new(0.1, Cartesian3::new(0.0, 2.0, 0.5), Cartesian3::new(-0.5, 0.0, 0.0), 0.1));
+    system
+}
+
+///Run forward, reverse the velocities, run the same amount of steps, and return the largest position error.
+fn round_trip<F>(steps: usize, step: F) -> f64
+    where F: Fn(&mut ParticleSystem<P, Cartesian3<f64>, f64>)
+{
+    let mut system = system();
+    let start: Vec<_> = system.iter().map(|p| p.position).collect();
+    for _ in 0..steps {
+        step(&mut system);
+    }
+    system.reverse_velocities();
+    for _ in 0..steps {
+        step(&mut system);
+    }
+    system.iter().zip(start).fold(0.0, |error, (p, s)| error.max((p.position - s).displacement()))
+}
+
+#[test]
+fn leapfrog_is_reversible() {
+    let error = round_trip(2000, |system| system.step_gravity_leapfrog(1.0, 0.001));
+    assert!(error < 1e-9, "leapfrog came back {} away from the start", error);
+}
+
+#[test]
+fn euler_is_not_reversible() {
+    //Semi-implicit Euler kicks before it drifts, so the reversed path does not retrace the forward one.
+    let leapfrog = round_trip(2000, |system| system.step_gravity_leapfrog(1.0, 0.001));
+    let euler = round_trip(2000, |system| system.step_gravity(1.0, 0.001));
+    assert!(euler > 1000.0 * leapfrog);
+}